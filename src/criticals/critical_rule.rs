@@ -0,0 +1,34 @@
+use crate::criticals::CriticalRule;
+use std::str::FromStr;
+
+impl FromStr for CriticalRule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        match rule.to_lowercase().as_str() {
+            "doubledice" => Ok(CriticalRule::DoubleDice),
+            "maxplusroll" => Ok(CriticalRule::MaxPlusRoll),
+            "rolltwice" => Ok(CriticalRule::RollTwice),
+            "doubletotal" => Ok(CriticalRule::DoubleTotal),
+            _ => Err(format!("Unknown critical rule `{rule}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_critical_rule() {
+        assert_eq!(
+            "maxPlusRoll".parse::<CriticalRule>(),
+            Ok(CriticalRule::MaxPlusRoll)
+        );
+        assert_eq!(
+            "DOUBLETOTAL".parse::<CriticalRule>(),
+            Ok(CriticalRule::DoubleTotal)
+        );
+        assert!("triple".parse::<CriticalRule>().is_err());
+    }
+}
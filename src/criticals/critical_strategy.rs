@@ -0,0 +1,160 @@
+use crate::criticals::{CriticalRule, CriticalStrategy};
+use crate::dice::{DiceRoller, Die, Roll};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct DoubleDiceStrategy;
+
+pub struct MaxPlusRollStrategy;
+
+pub struct RollTwiceStrategy;
+
+pub struct DoubleTotalStrategy;
+
+impl CriticalStrategy for DoubleDiceStrategy {
+    fn roll_critical(
+        &self,
+        dice_roller: &(dyn DiceRoller + Send + Sync),
+        dice: &[(Die, i32)],
+    ) -> (Vec<Roll>, i32) {
+        let doubled = dice
+            .iter()
+            .map(|(die, count)| match die {
+                Die::Raw => (die.clone(), *count),
+                die => (die.clone(), count * 2),
+            })
+            .collect::<Vec<_>>();
+        dice_roller.roll(&doubled)
+    }
+}
+
+impl CriticalStrategy for MaxPlusRollStrategy {
+    fn roll_critical(
+        &self,
+        dice_roller: &(dyn DiceRoller + Send + Sync),
+        dice: &[(Die, i32)],
+    ) -> (Vec<Roll>, i32) {
+        let (rolls, total) = dice_roller.roll(dice);
+        let maximums = dice
+            .iter()
+            .flat_map(|(die, count)| {
+                die.sides().map(|sides| {
+                    (0..count.abs())
+                        .map(|_| Roll::new(die.clone(), sides as i32 * count.signum()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        let maximum = maximums.iter().map(|roll| roll.value).sum::<i32>();
+        ([rolls, maximums].concat(), total + maximum)
+    }
+}
+
+impl CriticalStrategy for RollTwiceStrategy {
+    fn roll_critical(
+        &self,
+        dice_roller: &(dyn DiceRoller + Send + Sync),
+        dice: &[(Die, i32)],
+    ) -> (Vec<Roll>, i32) {
+        let damage_dice = dice
+            .iter()
+            .filter(|(die, _)| *die != Die::Raw)
+            .cloned()
+            .collect::<Vec<_>>();
+        let (first_rolls, first_total) = dice_roller.roll(dice);
+        let (second_rolls, second_total) = dice_roller.roll(&damage_dice);
+        (
+            [first_rolls, second_rolls].concat(),
+            first_total + second_total,
+        )
+    }
+}
+
+impl CriticalStrategy for DoubleTotalStrategy {
+    fn roll_critical(
+        &self,
+        dice_roller: &(dyn DiceRoller + Send + Sync),
+        dice: &[(Die, i32)],
+    ) -> (Vec<Roll>, i32) {
+        let (rolls, total) = dice_roller.roll(dice);
+        (rolls, total * 2)
+    }
+}
+
+pub fn build_critical_strategies() -> HashMap<CriticalRule, Arc<dyn CriticalStrategy + Send + Sync>>
+{
+    HashMap::from([
+        (
+            CriticalRule::DoubleDice,
+            Arc::new(DoubleDiceStrategy) as Arc<dyn CriticalStrategy + Send + Sync>,
+        ),
+        (CriticalRule::MaxPlusRoll, Arc::new(MaxPlusRollStrategy)),
+        (CriticalRule::RollTwice, Arc::new(RollTwiceStrategy)),
+        (CriticalRule::DoubleTotal, Arc::new(DoubleTotalStrategy)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::DieRoller;
+    use crate::dice::dice_roller::DiceRollerImpl;
+
+    struct MockDieRoller;
+    impl DieRoller for MockDieRoller {
+        fn roll(&self, _: &Die) -> i32 {
+            2
+        }
+
+        fn seeded(&self, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+            Arc::new(MockDieRoller)
+        }
+
+        fn seeded_stream(&self, _: u64, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+            Arc::new(MockDieRoller)
+        }
+    }
+
+    fn roll_critical(rule: CriticalRule) -> (Vec<Roll>, i32) {
+        build_critical_strategies()
+            .get(&rule)
+            .unwrap()
+            .roll_critical(
+                &DiceRollerImpl::new(Arc::new(MockDieRoller)),
+                &[(Die::D6, 2), (Die::Raw, 3)],
+            )
+    }
+
+    #[test]
+    fn test_double_dice() {
+        let (rolls, total) = roll_critical(CriticalRule::DoubleDice);
+
+        assert_eq!(rolls.len(), 5);
+        assert_eq!(total, 11);
+    }
+
+    #[test]
+    fn test_max_plus_roll() {
+        let (rolls, total) = roll_critical(CriticalRule::MaxPlusRoll);
+
+        assert_eq!(rolls.len(), 5);
+        assert_eq!(total, 19);
+    }
+
+    #[test]
+    fn test_roll_twice() {
+        let (rolls, total) = roll_critical(CriticalRule::RollTwice);
+
+        assert_eq!(rolls.len(), 5);
+        assert_eq!(total, 11);
+    }
+
+    #[test]
+    fn test_double_total() {
+        let (rolls, total) = roll_critical(CriticalRule::DoubleTotal);
+
+        assert_eq!(rolls.len(), 3);
+        assert_eq!(total, 14);
+    }
+}
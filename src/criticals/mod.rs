@@ -0,0 +1,25 @@
+pub mod critical_rule;
+pub mod critical_strategy;
+
+use crate::dice::{DiceRoller, Die, Roll};
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum CriticalRule {
+    #[serde(rename = "doubleDice")]
+    DoubleDice,
+    #[serde(rename = "maxPlusRoll")]
+    MaxPlusRoll,
+    #[serde(rename = "rollTwice")]
+    RollTwice,
+    #[serde(rename = "doubleTotal")]
+    DoubleTotal,
+}
+
+pub trait CriticalStrategy {
+    fn roll_critical(
+        &self,
+        dice_roller: &(dyn DiceRoller + Send + Sync),
+        dice: &[(Die, i32)],
+    ) -> (Vec<Roll>, i32);
+}
@@ -0,0 +1,196 @@
+use crate::derived::{DerivedStats, DerivedStatsCalculator, Spellcasting};
+use crate::monsters::{Monster, Size};
+use crate::stats::StatType;
+
+const PASSIVE_BASE: i32 = 10;
+const SPELL_SAVE_BASE: i32 = 8;
+const CARRYING_CAPACITY_PER_STRENGTH: i32 = 15;
+
+pub struct DerivedStatsCalculatorImpl {
+    passive_base: i32,
+    spell_save_base: i32,
+    carrying_capacity_per_strength: i32,
+}
+
+impl DerivedStatsCalculatorImpl {
+    fn new(passive_base: i32, spell_save_base: i32, carrying_capacity_per_strength: i32) -> Self {
+        Self {
+            passive_base,
+            spell_save_base,
+            carrying_capacity_per_strength,
+        }
+    }
+
+    pub fn default() -> Self {
+        Self::new(
+            PASSIVE_BASE,
+            SPELL_SAVE_BASE,
+            CARRYING_CAPACITY_PER_STRENGTH,
+        )
+    }
+}
+
+impl DerivedStatsCalculator for DerivedStatsCalculatorImpl {
+    fn derive(&self, monster: &Monster) -> DerivedStats {
+        let proficiency_bonus = proficiency_bonus(&monster.challenge.rating);
+        let passive = |skill: i32, stat: StatType| {
+            self.passive_base
+                + match skill {
+                    0 => modifier(monster, &stat),
+                    skill => skill,
+                }
+        };
+        let carrying_capacity = match &monster.size {
+            Size::Tiny => monster.stats.strength * self.carrying_capacity_per_strength / 2,
+            size => {
+                monster.stats.strength * self.carrying_capacity_per_strength * size_multiplier(size)
+            }
+        };
+        DerivedStats {
+            passive_perception: passive(monster.skills.perception, StatType::Wisdom),
+            passive_insight: passive(monster.skills.insight, StatType::Wisdom),
+            passive_investigation: passive(monster.skills.investigation, StatType::Intelligence),
+            initiative: monster.modifiers.dexterity,
+            proficiency_bonus,
+            spellcasting: spellcasting_ability(monster).map(|ability| Spellcasting {
+                save_dc: self.spell_save_base + proficiency_bonus + modifier(monster, &ability),
+                attack_bonus: proficiency_bonus + modifier(monster, &ability),
+                ability,
+            }),
+            carrying_capacity,
+            push_drag_lift: carrying_capacity * 2,
+        }
+    }
+}
+
+fn proficiency_bonus(rating: &str) -> i32 {
+    match rating.parse::<i32>() {
+        Ok(rating) if rating >= 5 => 2 + (rating - 1) / 4,
+        _ => 2,
+    }
+}
+
+fn size_multiplier(size: &Size) -> i32 {
+    match size {
+        Size::Tiny | Size::Small | Size::Medium => 1,
+        Size::Large => 2,
+        Size::Huge => 4,
+        Size::Gargantuan => 8,
+    }
+}
+
+fn modifier(monster: &Monster, stat: &StatType) -> i32 {
+    match stat {
+        StatType::Strength => monster.modifiers.strength,
+        StatType::Dexterity => monster.modifiers.dexterity,
+        StatType::Constitution => monster.modifiers.constitution,
+        StatType::Intelligence => monster.modifiers.intelligence,
+        StatType::Wisdom => monster.modifiers.wisdom,
+        StatType::Charisma => monster.modifiers.charisma,
+    }
+}
+
+fn spellcasting_ability(monster: &Monster) -> Option<StatType> {
+    monster
+        .traits
+        .iter()
+        .flat_map(|description| {
+            description
+                .split_once("spellcasting ability is ")
+                .map(|(_, rest)| rest)
+        })
+        .flat_map(|rest| {
+            let ability = rest
+                .split(|ch: char| !ch.is_alphabetic())
+                .next()
+                .unwrap_or_default();
+            match ability.to_lowercase().as_str() {
+                "strength" => Some(StatType::Strength),
+                "dexterity" => Some(StatType::Dexterity),
+                "constitution" => Some(StatType::Constitution),
+                "intelligence" => Some(StatType::Intelligence),
+                "wisdom" => Some(StatType::Wisdom),
+                "charisma" => Some(StatType::Charisma),
+                _ => None,
+            }
+        })
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::index::vec_to_map;
+    use crate::utilities::load_from_json::load_from_json;
+    use std::collections::HashMap;
+
+    fn monsters() -> HashMap<String, Monster> {
+        vec_to_map(
+            &load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH),
+            |monster| monster.name.clone(),
+        )
+    }
+
+    #[test]
+    fn test_proficiency_bonus() {
+        assert_eq!(proficiency_bonus("0"), 2);
+        assert_eq!(proficiency_bonus("1/4"), 2);
+        assert_eq!(proficiency_bonus("4"), 2);
+        assert_eq!(proficiency_bonus("5"), 3);
+        assert_eq!(proficiency_bonus("17"), 6);
+        assert_eq!(proficiency_bonus("30"), 9);
+    }
+
+    #[test]
+    fn test_derive() {
+        let derived = DerivedStatsCalculatorImpl::default().derive(&monsters()["Aboleth"]);
+
+        assert_eq!(
+            derived,
+            DerivedStats {
+                passive_perception: 20,
+                passive_insight: 12,
+                passive_investigation: 14,
+                initiative: -1,
+                proficiency_bonus: 4,
+                spellcasting: None,
+                carrying_capacity: 630,
+                push_drag_lift: 1260,
+            }
+        );
+    }
+
+    #[test]
+    fn test_derive_spellcasting_matches_stat_block() {
+        let monsters = monsters();
+        let calculator = DerivedStatsCalculatorImpl::default();
+
+        for (name, ability, save_dc) in [
+            ("Androsphinx", StatType::Wisdom, 18),
+            ("Archmage", StatType::Intelligence, 17),
+            ("Drow", StatType::Charisma, 11),
+        ] {
+            let spellcasting = calculator.derive(&monsters[name]).spellcasting.unwrap();
+
+            assert_eq!(spellcasting.ability, ability);
+            assert_eq!(spellcasting.save_dc, save_dc);
+        }
+    }
+
+    #[test]
+    fn test_derive_tiny_carrying_capacity() {
+        let monsters = monsters();
+        let tiny = monsters
+            .values()
+            .find(|monster| matches!(monster.size, Size::Tiny))
+            .unwrap();
+
+        assert_eq!(
+            DerivedStatsCalculatorImpl::default()
+                .derive(tiny)
+                .carrying_capacity,
+            tiny.stats.strength * 15 / 2
+        );
+    }
+}
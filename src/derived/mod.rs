@@ -0,0 +1,37 @@
+pub mod derived_stats_calculator;
+
+use crate::monsters::Monster;
+use crate::stats::StatType;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Spellcasting {
+    pub ability: StatType,
+    #[serde(rename = "saveDc")]
+    pub save_dc: i32,
+    #[serde(rename = "attackBonus")]
+    pub attack_bonus: i32,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DerivedStats {
+    #[serde(rename = "passivePerception")]
+    pub passive_perception: i32,
+    #[serde(rename = "passiveInsight")]
+    pub passive_insight: i32,
+    #[serde(rename = "passiveInvestigation")]
+    pub passive_investigation: i32,
+    pub initiative: i32,
+    #[serde(rename = "proficiencyBonus")]
+    pub proficiency_bonus: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spellcasting: Option<Spellcasting>,
+    #[serde(rename = "carryingCapacity")]
+    pub carrying_capacity: i32,
+    #[serde(rename = "pushDragLift")]
+    pub push_drag_lift: i32,
+}
+
+pub trait DerivedStatsCalculator {
+    fn derive(&self, monster: &Monster) -> DerivedStats;
+}
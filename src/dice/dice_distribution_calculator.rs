@@ -0,0 +1,424 @@
+use crate::dice::dice_roller::{MAX_EXPLOSIONS, MAX_REROLLS};
+use crate::dice::{
+    Comparison, DiceDistributionCalculator, DiceTerm, Distribution, Expression, Reroll, Selection,
+};
+use std::collections::{BTreeMap, HashMap};
+
+const MAX_OPERATIONS: u64 = 10_000_000;
+const NEGLIGIBLE_PROBABILITY: f64 = 1e-15;
+
+type Pmf = BTreeMap<i32, f64>;
+
+pub struct DiceDistributionCalculatorImpl {
+    max_operations: u64,
+}
+
+impl DiceDistributionCalculatorImpl {
+    fn new(max_operations: u64) -> Self {
+        Self { max_operations }
+    }
+
+    pub fn default() -> Self {
+        Self::new(MAX_OPERATIONS)
+    }
+}
+
+impl DiceDistributionCalculator for DiceDistributionCalculatorImpl {
+    fn distribution(&self, expression: &Expression) -> Result<Distribution, String> {
+        let mut analysis = Analysis {
+            remaining_operations: self.max_operations,
+        };
+        analysis
+            .expression(expression)
+            .map(|probabilities| Distribution { probabilities })
+    }
+}
+
+struct Analysis {
+    remaining_operations: u64,
+}
+
+impl Analysis {
+    fn spend(&mut self, operations: u64) -> Result<(), String> {
+        if operations > self.remaining_operations {
+            return Err("Expression is too complex to analyze exactly".to_string());
+        }
+        self.remaining_operations -= operations;
+        Ok(())
+    }
+
+    fn expression(&mut self, expression: &Expression) -> Result<Pmf, String> {
+        match expression {
+            Expression::Constant(value) => Ok(certain(*value)),
+            Expression::Dice(term) => self.term(term),
+            Expression::Negate(operand) => {
+                let operand = self.expression(operand)?;
+                self.spend(operand.len() as u64)?;
+                Ok(operand
+                    .into_iter()
+                    .fold(Pmf::new(), |mut pmf, (total, probability)| {
+                        *pmf.entry(total.saturating_neg()).or_insert(0.0) += probability;
+                        pmf
+                    }))
+            }
+            Expression::Binary(operator, left, right) => {
+                let left = self.expression(left)?;
+                let right = self.expression(right)?;
+                self.combine(&left, &right, |left, right| operator.apply(left, right))
+            }
+        }
+    }
+
+    fn combine(
+        &mut self,
+        left: &Pmf,
+        right: &Pmf,
+        operation: impl Fn(i32, i32) -> i32,
+    ) -> Result<Pmf, String> {
+        self.spend((left.len() * right.len()) as u64)?;
+        Ok(convolve(left, right, operation))
+    }
+
+    fn repeat(&mut self, unit: &Pmf, count: i32) -> Result<Pmf, String> {
+        let unit_span = match (unit.keys().next(), unit.keys().next_back()) {
+            (Some(min), Some(max)) => (max - min) as u64,
+            _ => 0,
+        };
+        let mut estimate = 0u64;
+        let mut result_span = 0u64;
+        let mut power_span = unit_span;
+        for step in repetition_steps(count) {
+            let (left_span, right_span) = match step {
+                RepetitionStep::Accumulate => (result_span, power_span),
+                RepetitionStep::Square => (power_span, power_span),
+            };
+            estimate = estimate.saturating_add((left_span + 1).saturating_mul(right_span + 1));
+            match step {
+                RepetitionStep::Accumulate => result_span = result_span.saturating_add(power_span),
+                RepetitionStep::Square => power_span = power_span.saturating_mul(2),
+            }
+        }
+        self.spend(estimate)?;
+        let mut result = certain(0);
+        let mut power = unit.clone();
+        for step in repetition_steps(count) {
+            match step {
+                RepetitionStep::Accumulate => {
+                    result = convolve(&result, &power, |left, right| left + right)
+                }
+                RepetitionStep::Square => {
+                    power = convolve(&power, &power, |left, right| left + right)
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn term(&mut self, term: &DiceTerm) -> Result<Pmf, String> {
+        let sides = term.die.sides().unwrap_or(1);
+        self.spend(sides as u64)?;
+        let uniform = uniform(sides);
+        let face = match &term.reroll {
+            None => uniform.clone(),
+            Some(reroll) => rerolled(sides, reroll),
+        };
+        let score = |value: i32| match &term.target {
+            None => value,
+            Some(target) => target.score(value),
+        };
+        match (&term.explosion, &term.selection) {
+            (Some(explosion), None) if !explosion.compounding => {
+                let unit = self.chain(&face, &face, &explosion.condition, score)?;
+                self.repeat(&unit, term.count)
+            }
+            (Some(explosion), Some(_)) if !explosion.compounding => Err(
+                "Exploding dice with keep or drop modifiers cannot be analyzed exactly".to_string(),
+            ),
+            (explosion, selection) => {
+                let unit = match explosion {
+                    None => face,
+                    Some(explosion) => {
+                        self.chain(&face, &uniform, &explosion.condition, |value| value)?
+                    }
+                };
+                match selection {
+                    None => {
+                        let mut scored = Pmf::new();
+                        for (value, probability) in unit {
+                            *scored.entry(score(value)).or_insert(0.0) += probability;
+                        }
+                        self.repeat(&scored, term.count)
+                    }
+                    Some(selection) => self.select(&unit, term.count as usize, selection, score),
+                }
+            }
+        }
+    }
+
+    fn chain(
+        &mut self,
+        first: &Pmf,
+        next: &Pmf,
+        condition: &Comparison,
+        score: impl Fn(i32) -> i32,
+    ) -> Result<Pmf, String> {
+        let mut result = Pmf::new();
+        let mut frontier = certain(0);
+        for depth in 0..=MAX_EXPLOSIONS {
+            let faces = if depth == 0 { first } else { next };
+            self.spend((frontier.len() * faces.len()) as u64)?;
+            let mut next_frontier = Pmf::new();
+            for (accumulated, probability) in &frontier {
+                for (face, face_probability) in faces {
+                    let destination = if condition.matches(*face) && depth < MAX_EXPLOSIONS {
+                        &mut next_frontier
+                    } else {
+                        &mut result
+                    };
+                    *destination.entry(accumulated + score(*face)).or_insert(0.0) +=
+                        probability * face_probability;
+                }
+            }
+            frontier = next_frontier
+                .into_iter()
+                .filter(|(_, probability)| *probability > NEGLIGIBLE_PROBABILITY)
+                .collect();
+            if frontier.is_empty() {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    fn select(
+        &mut self,
+        unit: &Pmf,
+        count: usize,
+        selection: &Selection,
+        score: impl Fn(i32) -> i32,
+    ) -> Result<Pmf, String> {
+        let (kept_count, highest) = selection.kept(count);
+        let mut ordered = unit.iter().collect::<Vec<_>>();
+        if highest {
+            ordered.reverse();
+        }
+        let mut states: Vec<HashMap<i32, f64>> = vec![HashMap::new(); count + 1];
+        states[0].insert(0, 1.0);
+        for (value, probability) in ordered {
+            let mut next_states: Vec<HashMap<i32, f64>> = vec![HashMap::new(); count + 1];
+            for (assigned, sums) in states.iter().enumerate() {
+                self.spend((sums.len() * (count - assigned + 1)) as u64)?;
+                for (sum, state_probability) in sums {
+                    let mut weight = 1.0;
+                    for chosen in 0..=(count - assigned) {
+                        if chosen > 0 {
+                            weight *= (count - assigned - chosen + 1) as f64 / chosen as f64
+                                * probability;
+                        }
+                        if weight == 0.0 {
+                            break;
+                        }
+                        let newly_kept = chosen.min(kept_count - assigned.min(kept_count));
+                        *next_states[assigned + chosen]
+                            .entry(sum + newly_kept as i32 * score(*value))
+                            .or_insert(0.0) += state_probability * weight;
+                    }
+                }
+            }
+            states = next_states;
+        }
+        Ok(states.pop().unwrap_or_default().into_iter().collect())
+    }
+}
+
+enum RepetitionStep {
+    Accumulate,
+    Square,
+}
+
+fn repetition_steps(count: i32) -> Vec<RepetitionStep> {
+    let mut steps = Vec::new();
+    let mut remaining = count;
+    while remaining > 0 {
+        if remaining % 2 == 1 {
+            steps.push(RepetitionStep::Accumulate);
+        }
+        remaining /= 2;
+        if remaining > 0 {
+            steps.push(RepetitionStep::Square);
+        }
+    }
+    steps
+}
+
+fn convolve(left: &Pmf, right: &Pmf, operation: impl Fn(i32, i32) -> i32) -> Pmf {
+    let mut combined = Pmf::new();
+    for (left_total, left_probability) in left {
+        for (right_total, right_probability) in right {
+            *combined
+                .entry(operation(*left_total, *right_total))
+                .or_insert(0.0) += left_probability * right_probability;
+        }
+    }
+    combined
+}
+
+fn certain(value: i32) -> Pmf {
+    Pmf::from([(value, 1.0)])
+}
+
+fn uniform(sides: u32) -> Pmf {
+    (1..=sides as i32)
+        .map(|face| (face, 1.0 / sides as f64))
+        .collect()
+}
+
+fn rerolled(sides: u32, reroll: &Reroll) -> Pmf {
+    let limit = if reroll.once { 1 } else { MAX_REROLLS as i32 };
+    let matching = (1..=sides as i32)
+        .filter(|face| reroll.condition.matches(*face))
+        .count() as f64;
+    let reroll_probability = matching / sides as f64;
+    let attempts = if reroll_probability == 1.0 {
+        limit as f64
+    } else {
+        (1.0 - reroll_probability.powi(limit)) / (1.0 - reroll_probability)
+    };
+    let exhausted = reroll_probability.powi(limit) / sides as f64;
+    (1..=sides as i32)
+        .map(|face| {
+            let accepted = if reroll.condition.matches(face) {
+                0.0
+            } else {
+                attempts / sides as f64
+            };
+            (face, accepted + exhausted)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::DiceExpressionParser;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+
+    fn distribution(expression: &str) -> Result<Distribution, String> {
+        let parsed = DiceExpressionParserImpl::default()
+            .parse_expression(expression)
+            .unwrap();
+        DiceDistributionCalculatorImpl::default().distribution(&parsed)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected} but found {actual}"
+        );
+    }
+
+    #[test]
+    fn test_sum_of_dice() {
+        let result = distribution("2d6+3").unwrap();
+
+        assert_eq!(result.min(), Some(5));
+        assert_eq!(result.max(), Some(15));
+        assert_close(result.mean(), 10.0);
+        assert_close(result.variance(), 35.0 / 6.0);
+        assert_close(result.probabilities[&10], 6.0 / 36.0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let result = distribution("-(1d4*2)/3").unwrap();
+
+        assert_eq!(
+            result.probabilities.keys().copied().collect::<Vec<_>>(),
+            vec![-3, -2, -1]
+        );
+        assert_close(result.probabilities[&-1], 0.25);
+        assert_close(result.probabilities[&-2], 0.5);
+    }
+
+    #[test]
+    fn test_negation_saturates() {
+        let result = distribution("-(0-2147483647-1)").unwrap();
+
+        assert_eq!(
+            result.probabilities.keys().copied().collect::<Vec<_>>(),
+            vec![i32::MAX]
+        );
+    }
+
+    #[test]
+    fn test_keep_highest() {
+        let result = distribution("2d20kh1").unwrap();
+
+        assert_close(result.probabilities[&20], 39.0 / 400.0);
+        assert_close(result.probabilities[&1], 1.0 / 400.0);
+        assert_close(result.mean(), 13.825);
+    }
+
+    #[test]
+    fn test_drop_lowest() {
+        let result = distribution("4d6dl1").unwrap();
+
+        assert_close(result.probabilities[&18], 21.0 / 1296.0);
+        assert_close(result.probabilities[&3], 1.0 / 1296.0);
+        assert_close(result.mean(), 15869.0 / 1296.0);
+    }
+
+    #[test]
+    fn test_reroll_once() {
+        let result = distribution("1d6ro<2").unwrap();
+
+        assert_close(result.probabilities[&1], 1.0 / 18.0);
+        assert_close(result.probabilities[&6], 1.0 / 6.0 + 1.0 / 18.0);
+    }
+
+    #[test]
+    fn test_reroll_until() {
+        let result = distribution("1d4r1").unwrap();
+
+        assert_close(result.probabilities.get(&1).copied().unwrap_or(0.0), 0.0);
+        assert_close(result.probabilities[&3], 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_exploding() {
+        let result = distribution("1d4!").unwrap();
+
+        assert_close(result.probabilities[&1], 0.25);
+        assert_close(result.probabilities[&5], 1.0 / 16.0);
+        assert_eq!(result.probabilities.get(&4), None);
+        assert_close(result.mean(), 10.0 / 3.0);
+    }
+
+    #[test]
+    fn test_success_pool() {
+        let result = distribution("3d6>=5f1").unwrap();
+
+        assert_close(result.probabilities[&3], 1.0 / 27.0);
+        assert_close(result.probabilities[&-3], 1.0 / 216.0);
+        assert_close(result.mean(), 3.0 * (2.0 / 6.0 - 1.0 / 6.0));
+    }
+
+    #[test]
+    fn test_exploding_selection_is_rejected() {
+        assert!(distribution("4d6!kh3").is_err());
+    }
+
+    #[test]
+    fn test_budget_exhaustion() {
+        let parsed = DiceExpressionParserImpl::default()
+            .parse_expression("20d6")
+            .unwrap();
+
+        assert!(
+            DiceDistributionCalculatorImpl::new(100)
+                .distribution(&parsed)
+                .is_err()
+        );
+        assert!(distribution("10000d1000").is_err());
+    }
+}
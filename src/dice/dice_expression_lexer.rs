@@ -0,0 +1,140 @@
+use crate::dice::DiceExpressionError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenKind {
+    Number(i32),
+    Word(String),
+    Symbol(char),
+    Reference(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: usize,
+}
+
+const SYMBOLS: [char; 11] = ['+', '-', '*', '/', '(', ')', '%', '!', '<', '>', '='];
+
+pub fn tokenize(expression: &str) -> Result<Vec<Token>, DiceExpressionError> {
+    let characters = expression.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < characters.len() {
+        let character = characters[position];
+        let start = position;
+        if character.is_whitespace() {
+            position += 1;
+        } else if character.is_ascii_digit() {
+            let digits = take_while(&characters, &mut position, |ch| ch.is_ascii_digit());
+            let number = digits.parse::<i32>().map_err(|_| DiceExpressionError {
+                expression: expression.to_string(),
+                position: start,
+                message: format!("Number `{digits}` is too large"),
+            })?;
+            tokens.push(Token {
+                kind: TokenKind::Number(number),
+                position: start,
+            });
+        } else if character.is_ascii_alphabetic() {
+            let word = take_while(&characters, &mut position, |ch| ch.is_ascii_alphabetic());
+            tokens.push(Token {
+                kind: TokenKind::Word(word.to_lowercase()),
+                position: start,
+            });
+        } else if character == '@' {
+            position += 1;
+            let name = take_while(&characters, &mut position, |ch| {
+                ch.is_ascii_alphanumeric() || *ch == '_'
+            });
+            if name.is_empty() {
+                return Err(DiceExpressionError {
+                    expression: expression.to_string(),
+                    position: start,
+                    message: "Expected a macro name after `@`".to_string(),
+                });
+            }
+            tokens.push(Token {
+                kind: TokenKind::Reference(name.to_lowercase()),
+                position: start,
+            });
+        } else if SYMBOLS.contains(&character) {
+            position += 1;
+            tokens.push(Token {
+                kind: TokenKind::Symbol(character),
+                position: start,
+            });
+        } else {
+            return Err(DiceExpressionError {
+                expression: expression.to_string(),
+                position: start,
+                message: format!("Unexpected character `{character}`"),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+fn take_while(characters: &[char], position: &mut usize, predicate: fn(&char) -> bool) -> String {
+    let taken = characters[*position..]
+        .iter()
+        .take_while(|ch| predicate(ch))
+        .collect::<String>();
+    *position += taken.chars().count();
+    taken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_dice_expression() {
+        let tokens = tokenize("2D6 + (3*4)").unwrap();
+
+        assert_eq!(
+            tokens
+                .into_iter()
+                .map(|token| (token.kind, token.position))
+                .collect::<Vec<_>>(),
+            vec![
+                (TokenKind::Number(2), 0),
+                (TokenKind::Word("d".to_string()), 1),
+                (TokenKind::Number(6), 2),
+                (TokenKind::Symbol('+'), 4),
+                (TokenKind::Symbol('('), 6),
+                (TokenKind::Number(3), 7),
+                (TokenKind::Symbol('*'), 8),
+                (TokenKind::Number(4), 9),
+                (TokenKind::Symbol(')'), 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unexpected_character() {
+        let error = tokenize("1d20+$").unwrap_err();
+
+        assert_eq!(error.position, 5);
+        assert_eq!(error.message, "Unexpected character `$`");
+    }
+
+    #[test]
+    fn test_tokenize_number_too_large() {
+        let error = tokenize("1+99999999999").unwrap_err();
+
+        assert_eq!(error.position, 2);
+    }
+
+    #[test]
+    fn test_tokenize_macro_reference() {
+        let tokens = tokenize("@Long_Sword2+1").unwrap();
+
+        assert_eq!(
+            tokens.first().map(|token| &token.kind),
+            Some(&TokenKind::Reference("long_sword2".to_string()))
+        );
+        assert_eq!(tokens.len(), 3);
+        assert!(tokenize("1+@").is_err());
+    }
+}
@@ -1,56 +1,577 @@
-use crate::dice::DiceExpressionParser;
-use crate::dice::Die;
-use regex::Regex;
+use crate::dice::dice_expression_lexer::{Token, TokenKind, tokenize};
+use crate::dice::dice_roller::MAX_EXPLOSIONS;
+use crate::dice::{
+    Comparison, DiceExpressionError, DiceExpressionParser, DiceTerm, Die, Explosion, Expression,
+    Operator, Reroll, Selection, SuccessTarget,
+};
+use crate::macros::MacroStore;
+use std::sync::Arc;
+
+const MAX_DICE: i32 = 10_000;
+const MAX_SIDES: u32 = 1_000_000;
+const MAX_DEPTH: usize = 64;
+const MAX_TOKENS: usize = 1_000;
 
 pub struct DiceExpressionParserImpl {
-    eval_regex: Regex,
+    max_dice: i32,
+    max_sides: u32,
+    max_depth: usize,
+    max_tokens: usize,
+    macro_store: Option<Arc<dyn MacroStore + Send + Sync>>,
 }
 
 impl DiceExpressionParserImpl {
-    fn new(eval_regex: Regex) -> Self {
-        Self { eval_regex }
+    fn new(
+        max_dice: i32,
+        max_sides: u32,
+        max_depth: usize,
+        max_tokens: usize,
+        macro_store: Option<Arc<dyn MacroStore + Send + Sync>>,
+    ) -> Self {
+        Self {
+            max_dice,
+            max_sides,
+            max_depth,
+            max_tokens,
+            macro_store,
+        }
     }
 
     pub fn default() -> Self {
-        Self::new(Regex::new(r"^-?(\d+)?(d4|d6|d8|d10|d12|d20)?$").unwrap())
+        Self::new(MAX_DICE, MAX_SIDES, MAX_DEPTH, MAX_TOKENS, None)
+    }
+
+    pub fn with_macros(self, macro_store: Arc<dyn MacroStore + Send + Sync>) -> Self {
+        Self {
+            macro_store: Some(macro_store),
+            ..self
+        }
+    }
+
+    fn parse_expanding(
+        &self,
+        expression: &str,
+        expanding: Vec<String>,
+        macro_store: Option<&(dyn MacroStore + Send + Sync)>,
+    ) -> Result<Expression, DiceExpressionError> {
+        let tokens = tokenize(expression)?;
+        if let Some(token) = tokens.get(self.max_tokens) {
+            return Err(DiceExpressionError {
+                expression: expression.to_string(),
+                position: token.position,
+                message: format!("Expression is longer than {} tokens", self.max_tokens),
+            });
+        }
+        Parser {
+            expression,
+            expanded_tokens: tokens.len(),
+            tokens,
+            index: 0,
+            depth: 0,
+            max_dice: self.max_dice,
+            max_sides: self.max_sides,
+            max_depth: self.max_depth,
+            max_tokens: self.max_tokens,
+            macro_store,
+            expanding,
+        }
+        .parse_all()
     }
 }
 
 impl DiceExpressionParser for DiceExpressionParserImpl {
-    fn parse(&self, expression: &str) -> Result<Vec<(Die, i32)>, String> {
-        let parsed_die_expression: Vec<(Die, i32)> = expression
-            .to_lowercase()
-            .replace('-', "+-")
-            .split("+")
-            .filter(|s| self.eval_regex.is_match(s))
-            .map(|s| {
-                if s.contains('d') {
-                    let parts = s.split('d').collect::<Vec<&str>>();
-                    return (
-                        match *parts.get(1).unwrap_or(&"") {
-                            "4" => Die::D4,
-                            "6" => Die::D6,
-                            "8" => Die::D8,
-                            "10" => Die::D10,
-                            "12" => Die::D12,
-                            _ => Die::D20,
-                        },
-                        parts.first().unwrap_or(&"0").parse::<i32>().unwrap_or(0),
-                    );
+    fn parse_expression(&self, expression: &str) -> Result<Expression, DiceExpressionError> {
+        self.parse_expanding(expression, vec![], self.macro_store.as_deref())
+    }
+
+    fn parse_macro(
+        &self,
+        name: &str,
+        expression: &str,
+        macros: &(dyn MacroStore + Send + Sync),
+    ) -> Result<Expression, DiceExpressionError> {
+        self.parse_expanding(expression, vec![name.to_lowercase()], Some(macros))
+    }
+}
+
+struct Parser<'a> {
+    expression: &'a str,
+    tokens: Vec<Token>,
+    index: usize,
+    depth: usize,
+    expanded_tokens: usize,
+    max_dice: i32,
+    max_sides: u32,
+    max_depth: usize,
+    max_tokens: usize,
+    macro_store: Option<&'a (dyn MacroStore + Send + Sync)>,
+    expanding: Vec<String>,
+}
+
+impl Parser<'_> {
+    fn parse_all(&mut self) -> Result<Expression, DiceExpressionError> {
+        let parsed_expression = self.parse_sum()?;
+        match self.peek() {
+            None => Ok(parsed_expression),
+            Some(token) => Err(self.unexpected(token)),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn peek_symbol(&self) -> Option<char> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Symbol(symbol),
+                ..
+            }) => Some(*symbol),
+            _ => None,
+        }
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn current_position(&self) -> usize {
+        self.peek()
+            .map(|token| token.position)
+            .unwrap_or_else(|| self.expression.chars().count())
+    }
+
+    fn error(&self, position: usize, message: String) -> DiceExpressionError {
+        DiceExpressionError {
+            expression: self.expression.to_string(),
+            position,
+            message,
+        }
+    }
+
+    fn unexpected(&self, token: &Token) -> DiceExpressionError {
+        let text = match &token.kind {
+            TokenKind::Number(number) => number.to_string(),
+            TokenKind::Word(word) => word.clone(),
+            TokenKind::Symbol(symbol) => symbol.to_string(),
+            TokenKind::Reference(name) => format!("@{name}"),
+        };
+        self.error(token.position, format!("Unexpected `{text}`"))
+    }
+
+    fn descend(&mut self) -> Result<(), DiceExpressionError> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(self.error(
+                self.current_position(),
+                format!("Expression is nested deeper than {}", self.max_depth),
+            ));
+        }
+        Ok(())
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression, DiceExpressionError> {
+        let mut left = self.parse_product()?;
+        while let Some(operator) = match self.peek_symbol() {
+            Some('+') => Some(Operator::Add),
+            Some('-') => Some(Operator::Subtract),
+            _ => None,
+        } {
+            self.advance();
+            let right = self.parse_product()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_product(&mut self) -> Result<Expression, DiceExpressionError> {
+        let mut left = self.parse_unary()?;
+        while let Some(operator) = match self.peek_symbol() {
+            Some('*') => Some(Operator::Multiply),
+            Some('/') => Some(Operator::Divide),
+            _ => None,
+        } {
+            self.advance();
+            let divisor_position = self.current_position();
+            let right = self.parse_unary()?;
+            if operator == Operator::Divide && right.constant_value() == Some(0) {
+                return Err(self.error(divisor_position, "Division by zero".to_string()));
+            }
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, DiceExpressionError> {
+        match self.peek_symbol() {
+            Some('-') => {
+                self.advance();
+                self.descend()?;
+                let operand = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(Expression::Negate(Box::new(operand)))
+            }
+            Some('+') => {
+                self.advance();
+                self.descend()?;
+                let operand = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(operand)
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, DiceExpressionError> {
+        let position = self.current_position();
+        match self.advance() {
+            None => Err(self.error(position, "Unexpected end of expression".to_string())),
+            Some(Token {
+                kind: TokenKind::Number(count),
+                position,
+            }) => match self.peek() {
+                Some(Token {
+                    kind: TokenKind::Word(word),
+                    ..
+                }) if word == "d" => {
+                    self.advance();
+                    self.parse_dice(count, position)
                 }
-                (Die::Raw, s.parse::<i32>().unwrap_or(0))
-            })
-            .collect();
-        if parsed_die_expression.is_empty() {
-            return Err(format!("\"{expression}\" is not a valid dice expression"));
+                _ => Ok(Expression::Constant(count)),
+            },
+            Some(Token {
+                kind: TokenKind::Word(word),
+                position,
+            }) if word == "d" => self.parse_dice(1, position),
+            Some(Token {
+                kind: TokenKind::Symbol('('),
+                ..
+            }) => {
+                self.descend()?;
+                let inner = self.parse_sum()?;
+                self.depth -= 1;
+                match self.advance() {
+                    Some(Token {
+                        kind: TokenKind::Symbol(')'),
+                        ..
+                    }) => Ok(inner),
+                    Some(token) => Err(self.unexpected(&token)),
+                    None => {
+                        Err(self.error(self.expression.chars().count(), "Expected `)`".to_string()))
+                    }
+                }
+            }
+            Some(Token {
+                kind: TokenKind::Reference(name),
+                position,
+            }) => self.parse_reference(name, position),
+            Some(token) => Err(self.unexpected(&token)),
+        }
+    }
+
+    fn parse_reference(
+        &mut self,
+        name: String,
+        position: usize,
+    ) -> Result<Expression, DiceExpressionError> {
+        if self.expanding.contains(&name) {
+            return Err(self.error(position, format!("Recursive macro `@{name}`")));
+        }
+        let Some(roll_macro) = self.macro_store.and_then(|macros| macros.get(&name)) else {
+            return Err(self.error(position, format!("Unknown macro `@{name}`")));
+        };
+        self.descend()?;
+        let tokens = tokenize(&roll_macro.expression).map_err(|error| {
+            self.error(position, format!("{} in macro `@{name}`", error.message))
+        })?;
+        let expanded_tokens = self.expanded_tokens + tokens.len();
+        if expanded_tokens > self.max_tokens {
+            return Err(self.error(
+                position,
+                format!("Macros expand to more than {} tokens", self.max_tokens),
+            ));
+        }
+        let mut expanding = self.expanding.clone();
+        expanding.push(name.clone());
+        let mut parser = Parser {
+            expression: &roll_macro.expression,
+            tokens,
+            index: 0,
+            depth: self.depth,
+            expanded_tokens,
+            max_dice: self.max_dice,
+            max_sides: self.max_sides,
+            max_depth: self.max_depth,
+            max_tokens: self.max_tokens,
+            macro_store: self.macro_store,
+            expanding,
+        };
+        let expanded = parser.parse_all().map_err(|error| {
+            self.error(position, format!("{} in macro `@{name}`", error.message))
+        })?;
+        self.expanded_tokens = parser.expanded_tokens;
+        self.depth -= 1;
+        Ok(expanded)
+    }
+
+    fn parse_dice(
+        &mut self,
+        count: i32,
+        position: usize,
+    ) -> Result<Expression, DiceExpressionError> {
+        if count > self.max_dice {
+            return Err(self.error(
+                position,
+                format!("Cannot roll more than {} dice at once", self.max_dice),
+            ));
+        }
+        let sides_position = self.current_position();
+        let sides = match self.advance() {
+            Some(Token {
+                kind: TokenKind::Number(sides),
+                ..
+            }) => sides as u32,
+            Some(Token {
+                kind: TokenKind::Symbol('%'),
+                ..
+            }) => 100,
+            Some(token) => return Err(self.unexpected(&token)),
+            None => {
+                return Err(self.error(sides_position, "Expected die sides".to_string()));
+            }
+        };
+        if sides == 0 || sides > self.max_sides {
+            return Err(self.error(
+                sides_position,
+                format!("Die sides must be between 1 and {}", self.max_sides),
+            ));
+        }
+        let mut term = DiceTerm::new(count, Die::from_sides(sides));
+        while let Some(token) = self.peek().cloned() {
+            match token.kind {
+                TokenKind::Word(word) => {
+                    self.advance();
+                    self.parse_word_modifier(&mut term, sides, &word, token.position)?;
+                }
+                TokenKind::Symbol('!') => {
+                    self.advance();
+                    self.parse_explosion(&mut term, sides, token.position)?;
+                }
+                TokenKind::Symbol('<' | '>' | '=') => {
+                    self.parse_target(&mut term, token.position)?;
+                }
+                _ => break,
+            }
+        }
+        let faces_per_die = match term.explosion {
+            None => 1,
+            Some(_) => MAX_EXPLOSIONS as i64 + 1,
+        };
+        if i64::from(term.count) * i64::from(sides) * faces_per_die > i64::from(i32::MAX) {
+            return Err(self.error(
+                position,
+                format!(
+                    "The total of {}d{sides} could exceed {}",
+                    term.count,
+                    i32::MAX
+                ),
+            ));
+        }
+        Ok(Expression::Dice(term))
+    }
+
+    fn parse_word_modifier(
+        &mut self,
+        term: &mut DiceTerm,
+        sides: u32,
+        word: &str,
+        position: usize,
+    ) -> Result<(), DiceExpressionError> {
+        match word {
+            "k" | "kh" | "kl" | "dh" | "dl" => {
+                if term.selection.is_some() {
+                    return Err(self.error(
+                        position,
+                        "Dice can only have one keep or drop modifier".to_string(),
+                    ));
+                }
+                let amount = self.parse_modifier_amount()?;
+                if amount > term.count as usize {
+                    return Err(self.error(
+                        position,
+                        format!("Cannot select {amount} of {} dice", term.count),
+                    ));
+                }
+                term.selection = Some(match word {
+                    "kl" => Selection::KeepLowest(amount),
+                    "dh" => Selection::DropHighest(amount),
+                    "dl" => Selection::DropLowest(amount),
+                    _ => Selection::KeepHighest(amount),
+                });
+                Ok(())
+            }
+            "r" | "ro" => {
+                if term.reroll.is_some() {
+                    return Err(self.error(
+                        position,
+                        "Dice can only have one reroll modifier".to_string(),
+                    ));
+                }
+                let once = word == "ro";
+                let condition = self.parse_comparison()?.unwrap_or(Comparison::Equal(1));
+                if !once && condition.matches_every_face(sides) {
+                    return Err(self.error(
+                        position,
+                        format!("Reroll condition matches every face of a d{sides}"),
+                    ));
+                }
+                term.reroll = Some(Reroll { once, condition });
+                Ok(())
+            }
+            "f" => match term.target {
+                None => Err(self.error(
+                    position,
+                    "Failure condition requires a success target".to_string(),
+                )),
+                Some(SuccessTarget {
+                    failure: Some(_), ..
+                }) => Err(self.error(
+                    position,
+                    "Dice can only have one failure condition".to_string(),
+                )),
+                Some(target) => {
+                    let failure = self.parse_comparison()?.unwrap_or(Comparison::Equal(1));
+                    term.target = Some(SuccessTarget {
+                        failure: Some(failure),
+                        ..target
+                    });
+                    Ok(())
+                }
+            },
+            _ => Err(self.error(position, format!("Unknown dice modifier `{word}`"))),
+        }
+    }
+
+    fn parse_target(
+        &mut self,
+        term: &mut DiceTerm,
+        position: usize,
+    ) -> Result<(), DiceExpressionError> {
+        if term.target.is_some() {
+            return Err(self.error(
+                position,
+                "Dice can only have one success target".to_string(),
+            ));
+        }
+        let success = match self.parse_comparison()? {
+            Some(success) => success,
+            None => return Err(self.error(position, "Expected a success target".to_string())),
+        };
+        term.target = Some(SuccessTarget {
+            success,
+            failure: None,
+        });
+        Ok(())
+    }
+
+    fn parse_explosion(
+        &mut self,
+        term: &mut DiceTerm,
+        sides: u32,
+        position: usize,
+    ) -> Result<(), DiceExpressionError> {
+        let compounding = self.peek_symbol() == Some('!');
+        if compounding {
+            self.advance();
+        }
+        if term.explosion.is_some() {
+            return Err(self.error(
+                position,
+                "Dice can only have one explosion modifier".to_string(),
+            ));
+        }
+        let condition = self
+            .parse_comparison()?
+            .unwrap_or(Comparison::AtLeast(sides as i32));
+        if condition.matches_every_face(sides) {
+            return Err(self.error(
+                position,
+                format!("Explosion condition matches every face of a d{sides}"),
+            ));
+        }
+        term.explosion = Some(Explosion {
+            compounding,
+            condition,
+        });
+        Ok(())
+    }
+
+    fn parse_comparison(&mut self) -> Result<Option<Comparison>, DiceExpressionError> {
+        match self.peek_symbol() {
+            Some('=') => {
+                self.advance();
+                Ok(Some(Comparison::Equal(self.expect_number()?)))
+            }
+            Some(symbol @ ('<' | '>')) => {
+                self.advance();
+                if self.peek_symbol() == Some('=') {
+                    self.advance();
+                }
+                let target = self.expect_number()?;
+                Ok(Some(match symbol {
+                    '<' => Comparison::AtMost(target),
+                    _ => Comparison::AtLeast(target),
+                }))
+            }
+            _ => match self.peek() {
+                Some(Token {
+                    kind: TokenKind::Number(target),
+                    ..
+                }) => {
+                    let target = *target;
+                    self.advance();
+                    Ok(Some(Comparison::Equal(target)))
+                }
+                _ => Ok(None),
+            },
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<i32, DiceExpressionError> {
+        let position = self.current_position();
+        match self.advance() {
+            Some(Token {
+                kind: TokenKind::Number(number),
+                ..
+            }) => Ok(number),
+            Some(token) => Err(self.unexpected(&token)),
+            None => Err(self.error(position, "Expected a number".to_string())),
+        }
+    }
+
+    fn parse_modifier_amount(&mut self) -> Result<usize, DiceExpressionError> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Number(amount),
+                ..
+            }) => {
+                let amount = *amount as usize;
+                self.advance();
+                Ok(amount)
+            }
+            _ => Ok(1),
         }
-        Ok(parsed_die_expression)
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dice::Die;
+    use crate::macros::{Macro, MacroError};
+    use std::collections::HashMap;
 
     #[test]
     fn test_dice_expression_parser_ok() {
@@ -100,4 +621,422 @@ mod tests {
             vec![(Die::D10, 4), (Die::D4, -2), (Die::Raw, -3),]
         );
     }
+
+    #[test]
+    fn test_dice_expression_rejects_unknown_terms() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let error = parser.parse_expression("2d6+banana+3").unwrap_err();
+        assert_eq!(error.position, 4);
+        assert_eq!(
+            error.to_string(),
+            "Unexpected `banana` at position 4 in \"2d6+banana+3\""
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_arbitrary_sides() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let result = parser.parse("1d2+d3+2d100+d7");
+        assert_eq!(
+            result.unwrap(),
+            vec![
+                (Die::Custom(2), 1),
+                (Die::Custom(3), 1),
+                (Die::D100, 2),
+                (Die::Custom(7), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_percentile_alias() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let result = parser.parse("d%+2D%");
+        assert_eq!(result.unwrap(), vec![(Die::D100, 1), (Die::D100, 2)]);
+    }
+
+    #[test]
+    fn test_dice_expression_rejects_zero_sided_die() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let error = parser.parse_expression("1d0").unwrap_err();
+        assert_eq!(error.position, 2);
+        assert!(parser.parse_expression("1d1000001").is_err());
+    }
+
+    #[test]
+    fn test_dice_expression_implicit_count() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let result = parser.parse("d20+1");
+        assert_eq!(result.unwrap(), vec![(Die::D20, 1), (Die::Raw, 1)]);
+    }
+
+    #[test]
+    fn test_dice_expression_precedence_and_parentheses() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let result = parser.parse_expression("-(1d6+2)*3/2").unwrap();
+        assert_eq!(
+            result,
+            Expression::Binary(
+                Operator::Divide,
+                Box::new(Expression::Binary(
+                    Operator::Multiply,
+                    Box::new(Expression::Negate(Box::new(Expression::Binary(
+                        Operator::Add,
+                        Box::new(Expression::Dice(DiceTerm::new(1, Die::D6))),
+                        Box::new(Expression::Constant(2)),
+                    )))),
+                    Box::new(Expression::Constant(3)),
+                )),
+                Box::new(Expression::Constant(2)),
+            )
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_compatibility_view_folds_constants() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let result = parser.parse("1d8+(2+3)*2-7/2");
+        assert_eq!(
+            result.unwrap(),
+            vec![(Die::D8, 1), (Die::Raw, 10), (Die::Raw, -3)]
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_compatibility_view_rejects_scaled_dice() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert!(parser.parse_expression("2d6*2").is_ok());
+        assert!(parser.parse("2d6*2").is_err());
+    }
+
+    #[test]
+    fn test_dice_expression_keep_and_drop() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let selections = ["4d6kh3", "2d20KL1", "4d6dl", "3d8dh1", "2d20k1"]
+            .iter()
+            .map(
+                |expression| match parser.parse_expression(expression).unwrap() {
+                    Expression::Dice(term) => term.selection,
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(
+            selections,
+            vec![
+                Some(Selection::KeepHighest(3)),
+                Some(Selection::KeepLowest(1)),
+                Some(Selection::DropLowest(1)),
+                Some(Selection::DropHighest(1)),
+                Some(Selection::KeepHighest(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_invalid_selection() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert_eq!(
+            parser.parse_expression("2d6kh3").unwrap_err().message,
+            "Cannot select 3 of 2 dice"
+        );
+        assert_eq!(
+            parser.parse_expression("4d6kh3dl1").unwrap_err().position,
+            6
+        );
+        assert_eq!(parser.parse_expression("4d6zz").unwrap_err().position, 3);
+    }
+
+    fn parse_term(parser: &DiceExpressionParserImpl, expression: &str) -> DiceTerm {
+        match parser.parse_expression(expression).unwrap() {
+            Expression::Dice(term) => term,
+            other => panic!("Expected a dice term but found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_dice_expression_explosions() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert_eq!(
+            parse_term(&parser, "3d6!").explosion,
+            Some(Explosion {
+                compounding: false,
+                condition: Comparison::AtLeast(6)
+            })
+        );
+        assert_eq!(
+            parse_term(&parser, "3d6!!>5").explosion,
+            Some(Explosion {
+                compounding: true,
+                condition: Comparison::AtLeast(5)
+            })
+        );
+        assert_eq!(
+            parse_term(&parser, "1d10!1").explosion,
+            Some(Explosion {
+                compounding: false,
+                condition: Comparison::Equal(1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_rerolls() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert_eq!(
+            parse_term(&parser, "2d6ro<2").reroll,
+            Some(Reroll {
+                once: true,
+                condition: Comparison::AtMost(2)
+            })
+        );
+        assert_eq!(
+            parse_term(&parser, "1d20r1").reroll,
+            Some(Reroll {
+                once: false,
+                condition: Comparison::Equal(1)
+            })
+        );
+        assert_eq!(
+            parse_term(&parser, "4d6r<=2kh3"),
+            DiceTerm {
+                selection: Some(Selection::KeepHighest(3)),
+                reroll: Some(Reroll {
+                    once: false,
+                    condition: Comparison::AtMost(2)
+                }),
+                ..DiceTerm::new(4, Die::D6)
+            }
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_rejects_endless_modifiers() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert!(parser.parse_expression("1d1!").is_err());
+        assert!(parser.parse_expression("1d6!>1").is_err());
+        assert!(parser.parse_expression("1d6r<6").is_err());
+        assert!(parser.parse_expression("1d6ro<6").is_ok());
+        assert!(parser.parse_expression("1d6!!").is_ok());
+        assert!(parser.parse_expression("1d6!!!").is_err());
+        assert!(parser.parse_expression("1d6r1r2").is_err());
+    }
+
+    #[test]
+    fn test_dice_expression_success_targets() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert_eq!(
+            parse_term(&parser, "10d6>=5").target,
+            Some(SuccessTarget {
+                success: Comparison::AtLeast(5),
+                failure: None
+            })
+        );
+        assert_eq!(
+            parse_term(&parser, "8d10>8f1").target,
+            Some(SuccessTarget {
+                success: Comparison::AtLeast(8),
+                failure: Some(Comparison::Equal(1))
+            })
+        );
+        assert_eq!(
+            parse_term(&parser, "5d6<=2f<=6").target,
+            Some(SuccessTarget {
+                success: Comparison::AtMost(2),
+                failure: Some(Comparison::AtMost(6))
+            })
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_invalid_success_targets() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert_eq!(parser.parse_expression("10d6f1").unwrap_err().position, 4);
+        assert!(parser.parse_expression("10d6>=5>=4").is_err());
+        assert!(parser.parse_expression("10d6>=5f1f2").is_err());
+        assert!(parser.parse_expression("10d6>=").is_err());
+    }
+
+    #[test]
+    fn test_dice_expression_unclosed_parenthesis() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let error = parser.parse_expression("(1d4+2").unwrap_err();
+        assert_eq!(error.position, 6);
+        assert_eq!(error.message, "Expected `)`");
+    }
+
+    #[test]
+    fn test_dice_expression_trailing_operator() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let error = parser.parse_expression("1d4+").unwrap_err();
+        assert_eq!(error.position, 4);
+        assert_eq!(error.message, "Unexpected end of expression");
+    }
+
+    #[test]
+    fn test_dice_expression_division_by_zero() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let error = parser.parse_expression("1d4/(2-2)").unwrap_err();
+        assert_eq!(error.position, 4);
+    }
+
+    #[test]
+    fn test_dice_expression_long_chain() {
+        let parser = DiceExpressionParserImpl::default();
+
+        let error = parser
+            .parse_expression(&vec!["1"; 15_000].join("+"))
+            .unwrap_err();
+        assert_eq!(error.message, "Expression is longer than 1000 tokens");
+        assert_eq!(error.position, 1000);
+        assert!(parser.parse_expression(&vec!["1"; 500].join("+")).is_ok());
+        assert!(parser.parse_expression(&vec!["1"; 501].join("+")).is_err());
+    }
+
+    #[test]
+    fn test_dice_expression_limits() {
+        let parser = DiceExpressionParserImpl::default();
+
+        assert!(parser.parse_expression("10001d6").is_err());
+        assert!(parser.parse_expression("10000d1000000").is_err());
+        assert!(parser.parse_expression("10000d100000").is_ok());
+        assert!(parser.parse_expression("10000d100000!").is_err());
+        assert!(parser.parse_expression(&"(".repeat(100)).is_err());
+    }
+
+    struct MockMacroStore {
+        macros: HashMap<String, String>,
+    }
+    impl MacroStore for MockMacroStore {
+        fn list(&self) -> Vec<Macro> {
+            vec![]
+        }
+
+        fn get(&self, name: &str) -> Option<Macro> {
+            self.macros.get(name).map(|expression| Macro {
+                name: name.to_string(),
+                expression: expression.clone(),
+            })
+        }
+
+        fn save(
+            &self,
+            roll_macro: Macro,
+            _: &dyn Fn(&Macro, &(dyn MacroStore + Send + Sync)) -> Result<(), String>,
+        ) -> Result<Macro, MacroError> {
+            Ok(roll_macro)
+        }
+
+        fn delete(&self, _: &str) -> Result<Option<Macro>, String> {
+            Ok(None)
+        }
+    }
+
+    fn mock_macros(macros: &[(&str, &str)]) -> MockMacroStore {
+        MockMacroStore {
+            macros: macros
+                .iter()
+                .map(|(name, expression)| (name.to_string(), expression.to_string()))
+                .collect(),
+        }
+    }
+
+    fn parser_with_macros(macros: &[(&str, &str)]) -> DiceExpressionParserImpl {
+        DiceExpressionParserImpl::default().with_macros(Arc::new(mock_macros(macros)))
+    }
+
+    #[test]
+    fn test_dice_expression_expands_macros() {
+        let parser = parser_with_macros(&[("longsword", "1d8+4"), ("smite", "@longsword+2d8")]);
+
+        assert_eq!(
+            parser.parse("@Smite+1").unwrap(),
+            vec![(Die::D8, 1), (Die::Raw, 4), (Die::D8, 2), (Die::Raw, 1)]
+        );
+        assert_eq!(
+            parser.parse_expression("2*@longsword").unwrap(),
+            Expression::Binary(
+                Operator::Multiply,
+                Box::new(Expression::Constant(2)),
+                Box::new(parser.parse_expression("(1d8+4)").unwrap()),
+            )
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_unknown_macro() {
+        let parser = parser_with_macros(&[]);
+
+        let error = parser.parse_expression("1d20+@missing").unwrap_err();
+        assert_eq!(error.position, 5);
+        assert_eq!(error.message, "Unknown macro `@missing`");
+        assert!(
+            DiceExpressionParserImpl::default()
+                .parse_expression("@missing")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_recursive_macro() {
+        let parser = parser_with_macros(&[("a", "@b+1"), ("b", "@a")]);
+
+        let error = parser.parse_expression("@a").unwrap_err();
+        assert_eq!(error.position, 0);
+        assert!(error.message.starts_with("Recursive macro `@a`"));
+        assert!(
+            parser
+                .parse_macro("b", "@a", &mock_macros(&[("a", "@b+1")]))
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse_macro("b", "@a", &mock_macros(&[("a", "1d4")]))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_dice_expression_macro_fan_out() {
+        let names = (0..20).map(|level| format!("m{level}")).collect::<Vec<_>>();
+        let expressions = (0..20)
+            .map(|level| match level {
+                0 => "1d4".to_string(),
+                level => format!("@m{}+@m{}", level - 1, level - 1),
+            })
+            .collect::<Vec<_>>();
+        let parser = parser_with_macros(
+            &names
+                .iter()
+                .zip(&expressions)
+                .map(|(name, expression)| (name.as_str(), expression.as_str()))
+                .collect::<Vec<_>>(),
+        );
+
+        assert!(parser.parse_expression("@m5").is_ok());
+        let error = parser.parse_expression("@m19").unwrap_err();
+        assert!(
+            error
+                .message
+                .starts_with("Macros expand to more than 1000 tokens")
+        );
+    }
 }
@@ -1,7 +1,11 @@
-use crate::dice::Die;
-use crate::dice::{DiceRoller, DieRoller, Roll};
+use crate::dice::{DiceRoller, DieRoller, Roll, RollResult};
+use crate::dice::{DiceTerm, Die, Expression, Operator, Selection};
+use std::collections::HashSet;
 use std::sync::Arc;
 
+pub const MAX_REROLLS: usize = 100;
+pub const MAX_EXPLOSIONS: usize = 100;
+
 pub struct DiceRollerImpl {
     die_roller: Arc<dyn DieRoller + Send + Sync>,
 }
@@ -10,6 +14,107 @@ impl DiceRollerImpl {
     pub fn new(die_roller: Arc<dyn DieRoller + Send + Sync>) -> Self {
         Self { die_roller }
     }
+
+    fn roll_term(&self, term: &DiceTerm) -> RollResult {
+        let rolls = (0..term.count)
+            .flat_map(|_| self.roll_die(term))
+            .collect::<Vec<Roll>>();
+        let rolls = match &term.selection {
+            None => rolls,
+            Some(selection) => select(rolls, selection),
+        };
+        match &term.target {
+            None => {
+                let total = rolls
+                    .iter()
+                    .filter(|roll| roll.kept)
+                    .map(|roll| roll.value)
+                    .sum();
+                RollResult {
+                    rolls,
+                    total,
+                    successes: None,
+                }
+            }
+            Some(target) => {
+                let rolls = rolls
+                    .into_iter()
+                    .map(|roll| Roll {
+                        outcome: roll.kept.then(|| target.outcome(roll.value)).flatten(),
+                        ..roll
+                    })
+                    .collect::<Vec<Roll>>();
+                let successes = rolls
+                    .iter()
+                    .map(|roll| roll.outcome.map_or(0, |outcome| outcome.score()))
+                    .sum();
+                RollResult {
+                    rolls,
+                    total: successes,
+                    successes: Some(successes),
+                }
+            }
+        }
+    }
+
+    fn roll_die(&self, term: &DiceTerm) -> Vec<Roll> {
+        let mut rolls = vec![self.roll_face(term)];
+        let Some(explosion) = &term.explosion else {
+            return rolls;
+        };
+        if explosion.compounding {
+            let roll = rolls.first_mut().unwrap();
+            let mut face = roll.value;
+            while explosion.condition.matches(face) && roll.compounded.len() < MAX_EXPLOSIONS {
+                face = self.die_roller.roll(&term.die);
+                roll.compounded.push(face);
+                roll.value += face;
+            }
+            return rolls;
+        }
+        while explosion.condition.matches(rolls.last().unwrap().value)
+            && rolls.len() <= MAX_EXPLOSIONS
+        {
+            rolls.push(Roll {
+                exploded: true,
+                ..self.roll_face(term)
+            });
+        }
+        rolls
+    }
+
+    fn roll_face(&self, term: &DiceTerm) -> Roll {
+        let mut roll = Roll::new(term.die.clone(), self.die_roller.roll(&term.die));
+        if let Some(reroll) = &term.reroll {
+            let limit = if reroll.once { 1 } else { MAX_REROLLS };
+            while reroll.condition.matches(roll.value) && roll.rerolled.len() < limit {
+                roll.rerolled.push(roll.value);
+                roll.value = self.die_roller.roll(&term.die);
+            }
+        }
+        roll
+    }
+}
+
+fn select(rolls: Vec<Roll>, selection: &Selection) -> Vec<Roll> {
+    let (kept_count, highest) = selection.kept(rolls.len());
+    let mut ranked = (0..rolls.len()).collect::<Vec<usize>>();
+    ranked.sort_by_key(|index| (rolls[*index].value, *index));
+    if highest {
+        ranked.reverse();
+    }
+    let kept_indices = ranked
+        .into_iter()
+        .take(kept_count)
+        .collect::<HashSet<usize>>();
+    rolls
+        .into_iter()
+        .enumerate()
+        .map(|(index, roll)| Roll {
+            kept: kept_indices.contains(&index),
+            ..roll
+        })
+        .collect()
 }
 
 impl DiceRoller for DiceRollerImpl {
@@ -17,15 +122,9 @@ impl DiceRoller for DiceRollerImpl {
         let rolls = dice
             .iter()
             .flat_map(|(die, n)| match die {
-                Die::Raw => vec![Roll {
-                    die: Die::Raw,
-                    value: *n,
-                }],
+                Die::Raw => vec![Roll::new(Die::Raw, *n)],
                 die => (0..*n)
-                    .map(|_| Roll {
-                        die: die.clone(),
-                        value: self.die_roller.roll(die),
-                    })
+                    .map(|_| Roll::new(die.clone(), self.die_roller.roll(die)))
                     .collect::<Vec<_>>(),
             })
             .collect::<Vec<Roll>>();
@@ -36,4 +135,402 @@ impl DiceRoller for DiceRollerImpl {
             .unwrap_or(0);
         (rolls, total)
     }
+
+    fn roll_expression(&self, expression: &Expression) -> RollResult {
+        match expression {
+            Expression::Constant(value) => RollResult {
+                rolls: vec![Roll::new(Die::Raw, *value)],
+                total: *value,
+                successes: None,
+            },
+            Expression::Dice(term) => self.roll_term(term),
+            Expression::Negate(operand) => {
+                let result = self.roll_expression(operand);
+                RollResult {
+                    total: result.total.saturating_neg(),
+                    successes: result.successes.map(i32::saturating_neg),
+                    ..result
+                }
+            }
+            Expression::Binary(operator, left, right) => {
+                let left = self.roll_expression(left);
+                let right = self.roll_expression(right);
+                RollResult {
+                    total: operator.apply(left.total, right.total),
+                    successes: match (operator, left.successes, right.successes) {
+                        (Operator::Multiply | Operator::Divide, _, _) => None,
+                        (_, None, None) => None,
+                        (operator, left, right) => {
+                            Some(operator.apply(left.unwrap_or(0), right.unwrap_or(0)))
+                        }
+                    },
+                    rolls: [left.rolls, right.rolls].concat(),
+                }
+            }
+        }
+    }
+
+    fn seeded(&self, seed: u64) -> Arc<dyn DiceRoller + Send + Sync> {
+        Arc::new(DiceRollerImpl::new(self.die_roller.seeded(seed)))
+    }
+
+    fn seeded_stream(&self, seed: u64, stream: u64) -> Arc<dyn DiceRoller + Send + Sync> {
+        Arc::new(DiceRollerImpl::new(
+            self.die_roller.seeded_stream(seed, stream),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::die_roller::DieRollerImpl;
+    use crate::dice::sequence_die_roller::SequenceDieRoller;
+    use crate::dice::{Comparison, Explosion, Outcome, Reroll, SuccessTarget};
+
+    struct MockDieRoller {}
+    impl DieRoller for MockDieRoller {
+        fn roll(&self, _: &Die) -> i32 {
+            5
+        }
+
+        fn seeded(&self, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+            Arc::new(MockDieRoller {})
+        }
+
+        fn seeded_stream(&self, _: u64, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+            Arc::new(MockDieRoller {})
+        }
+    }
+
+    #[test]
+    fn test_roll_expression() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(MockDieRoller {}));
+        let expression = Expression::Binary(
+            Operator::Divide,
+            Box::new(Expression::Binary(
+                Operator::Add,
+                Box::new(Expression::Dice(DiceTerm::new(2, Die::D6))),
+                Box::new(Expression::Constant(1)),
+            )),
+            Box::new(Expression::Constant(2)),
+        );
+
+        let RollResult { rolls, total, .. } = dice_roller.roll_expression(&expression);
+
+        assert_eq!(total, 5);
+        assert_eq!(
+            rolls,
+            vec![
+                Roll::new(Die::D6, 5),
+                Roll::new(Die::D6, 5),
+                Roll::new(Die::Raw, 1),
+                Roll::new(Die::Raw, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roll_expression_negate() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(MockDieRoller {}));
+        let expression = Expression::Negate(Box::new(Expression::Dice(DiceTerm::new(1, Die::D4))));
+
+        let RollResult { total, .. } = dice_roller.roll_expression(&expression);
+
+        assert_eq!(total, -5);
+    }
+
+    #[test]
+    fn test_roll_expression_negate_saturates() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(MockDieRoller {}));
+        let expression = Expression::Negate(Box::new(Expression::Binary(
+            Operator::Subtract,
+            Box::new(Expression::Constant(0)),
+            Box::new(Expression::Binary(
+                Operator::Add,
+                Box::new(Expression::Constant(i32::MAX)),
+                Box::new(Expression::Constant(1)),
+            )),
+        )));
+
+        let RollResult { total, .. } = dice_roller.roll_expression(&expression);
+
+        assert_eq!(total, i32::MAX);
+    }
+
+    #[test]
+    fn test_seeded_roll_expression_repeats() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(DieRollerImpl::default()));
+        let expression = Expression::Dice(DiceTerm::new(10, Die::D20));
+
+        let first = dice_roller.seeded(1234).roll_expression(&expression);
+        let second = dice_roller.seeded(1234).roll_expression(&expression);
+
+        assert_eq!(first, second);
+    }
+
+    fn roll_selection(values: Vec<i32>, selection: Selection) -> (Vec<bool>, i32) {
+        let dice_roller = DiceRollerImpl::new(Arc::new(SequenceDieRoller::new(values.clone())));
+        let expression = Expression::Dice(DiceTerm {
+            selection: Some(selection),
+            ..DiceTerm::new(values.len() as i32, Die::D6)
+        });
+        let RollResult { rolls, total, .. } = dice_roller.roll_expression(&expression);
+        (rolls.iter().map(|roll| roll.kept).collect(), total)
+    }
+
+    #[test]
+    fn test_roll_keep_highest() {
+        assert_eq!(
+            roll_selection(vec![3, 1, 6, 4], Selection::KeepHighest(3)),
+            (vec![true, false, true, true], 13)
+        );
+    }
+
+    #[test]
+    fn test_roll_keep_lowest() {
+        assert_eq!(
+            roll_selection(vec![15, 4], Selection::KeepLowest(1)),
+            (vec![false, true], 4)
+        );
+    }
+
+    #[test]
+    fn test_roll_drop_highest() {
+        assert_eq!(
+            roll_selection(vec![2, 5, 5], Selection::DropHighest(1)),
+            (vec![true, true, false], 7)
+        );
+    }
+
+    #[test]
+    fn test_roll_drop_lowest_with_ties() {
+        assert_eq!(
+            roll_selection(vec![2, 2, 5, 6], Selection::DropLowest(1)),
+            (vec![false, true, true, true], 13)
+        );
+    }
+
+    fn roll_term_with(values: Vec<i32>, term: DiceTerm) -> RollResult {
+        let dice_roller = DiceRollerImpl::new(Arc::new(SequenceDieRoller::new(values)));
+        dice_roller.roll_expression(&Expression::Dice(term))
+    }
+
+    #[test]
+    fn test_roll_exploding() {
+        let RollResult { rolls, total, .. } = roll_term_with(
+            vec![6, 6, 2, 3],
+            DiceTerm {
+                explosion: Some(Explosion {
+                    compounding: false,
+                    condition: Comparison::AtLeast(6),
+                }),
+                ..DiceTerm::new(2, Die::D6)
+            },
+        );
+
+        assert_eq!(total, 17);
+        assert_eq!(
+            rolls,
+            vec![
+                Roll::new(Die::D6, 6),
+                Roll {
+                    exploded: true,
+                    ..Roll::new(Die::D6, 6)
+                },
+                Roll {
+                    exploded: true,
+                    ..Roll::new(Die::D6, 2)
+                },
+                Roll::new(Die::D6, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roll_compounding() {
+        let RollResult { rolls, total, .. } = roll_term_with(
+            vec![6, 6, 4],
+            DiceTerm {
+                explosion: Some(Explosion {
+                    compounding: true,
+                    condition: Comparison::AtLeast(6),
+                }),
+                ..DiceTerm::new(1, Die::D6)
+            },
+        );
+
+        assert_eq!(total, 16);
+        assert_eq!(
+            rolls,
+            vec![Roll {
+                compounded: vec![6, 4],
+                ..Roll::new(Die::D6, 16)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_roll_reroll_once() {
+        let RollResult { rolls, total, .. } = roll_term_with(
+            vec![1, 2, 5],
+            DiceTerm {
+                reroll: Some(Reroll {
+                    once: true,
+                    condition: Comparison::AtMost(2),
+                }),
+                ..DiceTerm::new(2, Die::D6)
+            },
+        );
+
+        assert_eq!(total, 7);
+        assert_eq!(
+            rolls,
+            vec![
+                Roll {
+                    rerolled: vec![1],
+                    ..Roll::new(Die::D6, 2)
+                },
+                Roll::new(Die::D6, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roll_reroll_until() {
+        let RollResult { rolls, total, .. } = roll_term_with(
+            vec![1, 1, 1, 4],
+            DiceTerm {
+                reroll: Some(Reroll {
+                    once: false,
+                    condition: Comparison::Equal(1),
+                }),
+                ..DiceTerm::new(1, Die::D20)
+            },
+        );
+
+        assert_eq!(total, 4);
+        assert_eq!(rolls.first().unwrap().rerolled, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_roll_explosion_cap() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(MockDieRoller {}));
+        let RollResult { rolls, total, .. } =
+            dice_roller.roll_expression(&Expression::Dice(DiceTerm {
+                explosion: Some(Explosion {
+                    compounding: false,
+                    condition: Comparison::AtLeast(5),
+                }),
+                ..DiceTerm::new(1, Die::D6)
+            }));
+
+        assert_eq!(rolls.len(), MAX_EXPLOSIONS + 1);
+        assert_eq!(total, 5 * (MAX_EXPLOSIONS as i32 + 1));
+    }
+
+    #[test]
+    fn test_roll_success_pool() {
+        let result = roll_term_with(
+            vec![5, 1, 3, 6],
+            DiceTerm {
+                target: Some(SuccessTarget {
+                    success: Comparison::AtLeast(5),
+                    failure: Some(Comparison::Equal(1)),
+                }),
+                ..DiceTerm::new(4, Die::D6)
+            },
+        );
+
+        assert_eq!(result.total, 1);
+        assert_eq!(result.successes, Some(1));
+        assert_eq!(
+            result
+                .rolls
+                .iter()
+                .map(|roll| roll.outcome)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Outcome::Success),
+                Some(Outcome::Failure),
+                None,
+                Some(Outcome::Success)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roll_success_pool_ignores_dropped_dice() {
+        let result = roll_term_with(
+            vec![6, 2],
+            DiceTerm {
+                selection: Some(Selection::KeepLowest(1)),
+                target: Some(SuccessTarget {
+                    success: Comparison::AtLeast(5),
+                    failure: None,
+                }),
+                ..DiceTerm::new(2, Die::D6)
+            },
+        );
+
+        assert_eq!(result.successes, Some(0));
+        assert_eq!(result.rolls.first().unwrap().outcome, None);
+    }
+
+    fn success_pool(count: i32) -> Expression {
+        Expression::Dice(DiceTerm {
+            target: Some(SuccessTarget {
+                success: Comparison::AtLeast(5),
+                failure: None,
+            }),
+            ..DiceTerm::new(count, Die::D6)
+        })
+    }
+
+    #[test]
+    fn test_roll_negated_success_pool() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(SequenceDieRoller::new(vec![5, 6, 2])));
+
+        let result = dice_roller.roll_expression(&Expression::Negate(Box::new(success_pool(3))));
+
+        assert_eq!(result.total, -2);
+        assert_eq!(result.successes, Some(-2));
+    }
+
+    #[test]
+    fn test_roll_subtracted_success_pool() {
+        let dice_roller = DiceRollerImpl::new(Arc::new(SequenceDieRoller::new(vec![5, 6, 2, 6])));
+
+        let result = dice_roller.roll_expression(&Expression::Binary(
+            Operator::Subtract,
+            Box::new(success_pool(3)),
+            Box::new(success_pool(1)),
+        ));
+
+        assert_eq!(result.total, 1);
+        assert_eq!(result.successes, Some(1));
+    }
+
+    #[test]
+    fn test_roll_scaled_success_pool_has_no_successes() {
+        for operator in [Operator::Multiply, Operator::Divide] {
+            let dice_roller =
+                DiceRollerImpl::new(Arc::new(SequenceDieRoller::new(vec![5, 6, 2, 1])));
+
+            let result = dice_roller.roll_expression(&Expression::Binary(
+                operator,
+                Box::new(success_pool(4)),
+                Box::new(Expression::Constant(3)),
+            ));
+
+            assert_eq!(result.successes, None);
+        }
+    }
+
+    #[test]
+    fn test_roll_without_pool_has_no_successes() {
+        let result = roll_term_with(vec![4], DiceTerm::new(1, Die::D6));
+
+        assert_eq!(result.successes, None);
+    }
 }
@@ -0,0 +1,90 @@
+use crate::dice::Die;
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+impl Die {
+    pub fn from_sides(sides: u32) -> Die {
+        match sides {
+            4 => Die::D4,
+            6 => Die::D6,
+            8 => Die::D8,
+            10 => Die::D10,
+            12 => Die::D12,
+            20 => Die::D20,
+            100 => Die::D100,
+            sides => Die::Custom(sides),
+        }
+    }
+
+    pub fn sides(&self) -> Option<u32> {
+        match self {
+            Die::D4 => Some(4),
+            Die::D6 => Some(6),
+            Die::D8 => Some(8),
+            Die::D10 => Some(10),
+            Die::D12 => Some(12),
+            Die::D20 => Some(20),
+            Die::D100 => Some(100),
+            Die::Raw => None,
+            Die::Custom(sides) => Some(*sides),
+        }
+    }
+
+    pub fn standard() -> Vec<Die> {
+        vec![
+            Die::D4,
+            Die::D6,
+            Die::D8,
+            Die::D10,
+            Die::D12,
+            Die::D20,
+            Die::D100,
+            Die::Raw,
+        ]
+    }
+}
+
+impl Display for Die {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.sides() {
+            None => write!(f, "Raw"),
+            Some(sides) => write!(f, "D{sides}"),
+        }
+    }
+}
+
+impl Serialize for Die {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sides_prefers_named_dice() {
+        assert_eq!(Die::from_sides(20), Die::D20);
+        assert_eq!(Die::from_sides(100), Die::D100);
+        assert_eq!(Die::from_sides(3), Die::Custom(3));
+    }
+
+    #[test]
+    fn test_sides() {
+        assert_eq!(Die::D8.sides(), Some(8));
+        assert_eq!(Die::Custom(7).sides(), Some(7));
+        assert_eq!(Die::Raw.sides(), None);
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(
+            serde_json::to_string(&vec![Die::D6, Die::Custom(3), Die::Raw]).unwrap(),
+            "[\"D6\",\"D3\",\"Raw\"]"
+        );
+    }
+}
@@ -1,44 +1,56 @@
 use crate::dice::Die;
 use crate::dice::DieRoller;
-use rand::Rng;
-use rand::rngs::ThreadRng;
-use std::cell::RefCell;
-use std::thread::LocalKey;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::sync::{Arc, Mutex};
 
-pub struct DieRollerImpl {
-    random: &'static LocalKey<RefCell<ThreadRng>>,
-}
+const MAX_SAFE_SEED: u64 = (1 << 53) - 1;
 
-thread_local! {
-    static RNG: RefCell<ThreadRng> = RefCell::new(rand::rng());
+pub struct DieRollerImpl {
+    random: Mutex<ChaCha8Rng>,
 }
 
 impl DieRollerImpl {
-    fn new(random: &'static LocalKey<RefCell<ThreadRng>>) -> Self {
-        Self { random }
+    fn new(random: ChaCha8Rng) -> Self {
+        Self {
+            random: Mutex::new(random),
+        }
     }
 
     pub fn default() -> Self {
-        Self::new(&RNG)
+        Self::new(ChaCha8Rng::from_os_rng())
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    pub fn from_seed_stream(seed: u64, stream: u64) -> Self {
+        let mut random = ChaCha8Rng::seed_from_u64(seed);
+        random.set_stream(stream);
+        Self::new(random)
     }
 }
 
 impl DieRoller for DieRollerImpl {
     fn roll(&self, die: &Die) -> i32 {
-        let get_local_inclusive_random = |n| {
-            self.random
-                .with(|cell| cell.borrow_mut().random_range(1..=n))
-        };
-        match die {
-            Die::D4 => get_local_inclusive_random(4),
-            Die::D6 => get_local_inclusive_random(6),
-            Die::D8 => get_local_inclusive_random(8),
-            Die::D10 => get_local_inclusive_random(10),
-            Die::D12 => get_local_inclusive_random(12),
-            Die::D20 => get_local_inclusive_random(20),
-            Die::Raw => 0,
+        match die.sides() {
+            None => 0,
+            Some(sides) => self.random.lock().unwrap().random_range(1..=sides as i32),
         }
     }
+
+    fn seeded(&self, seed: u64) -> Arc<dyn DieRoller + Send + Sync> {
+        Arc::new(DieRollerImpl::from_seed(seed))
+    }
+
+    fn seeded_stream(&self, seed: u64, stream: u64) -> Arc<dyn DieRoller + Send + Sync> {
+        Arc::new(DieRollerImpl::from_seed_stream(seed, stream))
+    }
+}
+
+pub fn random_seed() -> u64 {
+    rand::random::<u64>() & MAX_SAFE_SEED
 }
 
 #[cfg(test)]
@@ -87,10 +99,57 @@ mod tests {
         assert!(matches!(roller.roll(&Die::D20), 1..=20));
     }
 
+    #[test]
+    fn test_roll_d100() {
+        let roller = DieRollerImpl::default();
+
+        assert!(matches!(roller.roll(&Die::D100), 1..=100));
+    }
+
+    #[test]
+    fn test_roll_custom() {
+        let roller = DieRollerImpl::default();
+
+        assert!(matches!(roller.roll(&Die::Custom(3)), 1..=3));
+        assert_eq!(roller.roll(&Die::Custom(1)), 1);
+    }
+
     #[test]
     fn test_roll_raw() {
         let roller = DieRollerImpl::default();
 
         assert_eq!(roller.roll(&Die::Raw), 0)
     }
+
+    #[test]
+    fn test_seeded_rolls_repeat() {
+        let first = DieRollerImpl::from_seed(42);
+        let second = DieRollerImpl::default().seeded(42);
+
+        let first_rolls = (0..20).map(|_| first.roll(&Die::D20)).collect::<Vec<_>>();
+        let second_rolls = (0..20).map(|_| second.roll(&Die::D20)).collect::<Vec<_>>();
+
+        assert_eq!(first_rolls, second_rolls);
+    }
+
+    #[test]
+    fn test_seeded_streams_do_not_overlap() {
+        let rolls = |roller: Arc<dyn DieRoller + Send + Sync>| {
+            (0..20).map(|_| roller.roll(&Die::D20)).collect::<Vec<_>>()
+        };
+        let roller = DieRollerImpl::default();
+
+        assert_eq!(rolls(roller.seeded_stream(42, 0)), rolls(roller.seeded(42)));
+        assert_ne!(rolls(roller.seeded_stream(42, 1)), rolls(roller.seeded(42)));
+        assert_ne!(rolls(roller.seeded_stream(42, 1)), rolls(roller.seeded(43)));
+        assert_eq!(
+            rolls(roller.seeded_stream(42, 1)),
+            rolls(roller.seeded_stream(42, 1))
+        );
+    }
+
+    #[test]
+    fn test_random_seed_is_safe_integer() {
+        assert!((0..100).all(|_| random_seed() <= MAX_SAFE_SEED));
+    }
 }
@@ -0,0 +1,101 @@
+use crate::dice::Distribution;
+
+impl Distribution {
+    pub fn total_probability(&self) -> f64 {
+        self.probabilities.values().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.probabilities
+            .iter()
+            .map(|(total, probability)| *total as f64 * probability)
+            .sum::<f64>()
+            / self.total_probability()
+    }
+
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.probabilities
+            .iter()
+            .map(|(total, probability)| (*total as f64 - mean).powi(2) * probability)
+            .sum::<f64>()
+            / self.total_probability()
+    }
+
+    pub fn min(&self) -> Option<i32> {
+        self.probabilities.keys().next().copied()
+    }
+
+    pub fn max(&self) -> Option<i32> {
+        self.probabilities.keys().next_back().copied()
+    }
+
+    pub fn percentile(&self, percent: u32) -> Option<i32> {
+        let target = self.total_probability() * percent as f64 / 100.0;
+        let mut cumulative = 0.0;
+        self.probabilities
+            .iter()
+            .find(|(_, probability)| {
+                cumulative += *probability;
+                cumulative >= target - f64::EPSILON
+            })
+            .map(|(total, _)| *total)
+            .or_else(|| self.max())
+    }
+
+    pub fn probability_at_least(&self, threshold: i32) -> f64 {
+        self.probabilities
+            .range(threshold..)
+            .map(|(_, probability)| probability)
+            .sum::<f64>()
+            / self.total_probability()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn two_d_four() -> Distribution {
+        Distribution {
+            probabilities: BTreeMap::from([
+                (2, 1.0 / 16.0),
+                (3, 2.0 / 16.0),
+                (4, 3.0 / 16.0),
+                (5, 4.0 / 16.0),
+                (6, 3.0 / 16.0),
+                (7, 2.0 / 16.0),
+                (8, 1.0 / 16.0),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_moments() {
+        let distribution = two_d_four();
+
+        assert!((distribution.mean() - 5.0).abs() < 1e-9);
+        assert!((distribution.variance() - 2.5).abs() < 1e-9);
+        assert_eq!(distribution.min(), Some(2));
+        assert_eq!(distribution.max(), Some(8));
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let distribution = two_d_four();
+
+        assert_eq!(distribution.percentile(0), Some(2));
+        assert_eq!(distribution.percentile(50), Some(5));
+        assert_eq!(distribution.percentile(100), Some(8));
+    }
+
+    #[test]
+    fn test_probability_at_least() {
+        let distribution = two_d_four();
+
+        assert!((distribution.probability_at_least(7) - 3.0 / 16.0).abs() < 1e-9);
+        assert!((distribution.probability_at_least(2) - 1.0).abs() < 1e-9);
+        assert_eq!(distribution.probability_at_least(9), 0.0);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::dice::dice_roller::{MAX_EXPLOSIONS, MAX_REROLLS};
+use crate::dice::{
+    Comparison, DiceTerm, Die, Expression, Operator, Outcome, Selection, SuccessTarget,
+};
+
+impl Expression {
+    pub fn constant_value(&self) -> Option<i32> {
+        match self {
+            Expression::Constant(value) => Some(*value),
+            Expression::Dice(_) => None,
+            Expression::Negate(operand) => operand.constant_value().map(i32::saturating_neg),
+            Expression::Binary(operator, left, right) => left
+                .constant_value()
+                .zip(right.constant_value())
+                .map(|(left, right)| operator.apply(left, right)),
+        }
+    }
+
+    pub fn max_rolls(&self) -> u64 {
+        match self {
+            Expression::Constant(_) => 0,
+            Expression::Dice(term) => term.max_rolls(),
+            Expression::Negate(operand) => operand.max_rolls(),
+            Expression::Binary(_, left, right) => {
+                left.max_rolls().saturating_add(right.max_rolls())
+            }
+        }
+    }
+
+    pub fn terms(&self) -> Result<Vec<(Die, i32)>, String> {
+        if let Some(value) = self.constant_value() {
+            return Ok(vec![(Die::Raw, value)]);
+        }
+        match self {
+            Expression::Constant(value) => Ok(vec![(Die::Raw, *value)]),
+            Expression::Dice(term) if term.has_modifiers() => {
+                Err("Dice modifiers cannot be represented as a sum of dice terms".to_string())
+            }
+            Expression::Dice(term) => Ok(vec![(term.die.clone(), term.count)]),
+            Expression::Negate(operand) => operand.terms().map(negate_terms),
+            Expression::Binary(Operator::Add, left, right) => {
+                Ok([left.terms()?, right.terms()?].concat())
+            }
+            Expression::Binary(Operator::Subtract, left, right) => {
+                Ok([left.terms()?, negate_terms(right.terms()?)].concat())
+            }
+            Expression::Binary(_, _, _) => {
+                Err("Scaled dice cannot be represented as a sum of dice terms".to_string())
+            }
+        }
+    }
+}
+
+impl DiceTerm {
+    pub fn new(count: i32, die: Die) -> Self {
+        Self {
+            count,
+            die,
+            selection: None,
+            explosion: None,
+            reroll: None,
+            target: None,
+        }
+    }
+
+    pub fn max_rolls(&self) -> u64 {
+        let faces = match self.explosion {
+            None => 1,
+            Some(_) => MAX_EXPLOSIONS as u64 + 1,
+        };
+        let attempts = match &self.reroll {
+            None => 1,
+            Some(reroll) if reroll.once => 2,
+            Some(_) => MAX_REROLLS as u64 + 1,
+        };
+        self.count.max(0) as u64 * faces * attempts
+    }
+
+    pub fn has_modifiers(&self) -> bool {
+        self.selection.is_some()
+            || self.explosion.is_some()
+            || self.reroll.is_some()
+            || self.target.is_some()
+    }
+}
+
+impl SuccessTarget {
+    pub fn outcome(&self, value: i32) -> Option<Outcome> {
+        if self.success.matches(value) {
+            Some(Outcome::Success)
+        } else if self.failure.is_some_and(|failure| failure.matches(value)) {
+            Some(Outcome::Failure)
+        } else {
+            None
+        }
+    }
+
+    pub fn score(&self, value: i32) -> i32 {
+        self.outcome(value).map_or(0, |outcome| outcome.score())
+    }
+}
+
+impl Outcome {
+    pub fn score(&self) -> i32 {
+        match self {
+            Outcome::Success => 1,
+            Outcome::Failure => -1,
+        }
+    }
+}
+
+impl Comparison {
+    pub fn matches(&self, value: i32) -> bool {
+        match self {
+            Comparison::Equal(target) => value == *target,
+            Comparison::AtMost(target) => value <= *target,
+            Comparison::AtLeast(target) => value >= *target,
+        }
+    }
+
+    pub fn matches_every_face(&self, sides: u32) -> bool {
+        match self {
+            Comparison::Equal(target) => sides == 1 && *target == 1,
+            Comparison::AtMost(target) => *target >= sides as i32,
+            Comparison::AtLeast(target) => *target <= 1,
+        }
+    }
+}
+
+impl Selection {
+    pub fn kept(&self, rolled: usize) -> (usize, bool) {
+        match self {
+            Selection::KeepHighest(n) => (*n.min(&rolled), true),
+            Selection::KeepLowest(n) => (*n.min(&rolled), false),
+            Selection::DropHighest(n) => (rolled.saturating_sub(*n), false),
+            Selection::DropLowest(n) => (rolled.saturating_sub(*n), true),
+        }
+    }
+}
+
+impl Operator {
+    pub fn apply(&self, left: i32, right: i32) -> i32 {
+        match self {
+            Operator::Add => left.saturating_add(right),
+            Operator::Subtract => left.saturating_sub(right),
+            Operator::Multiply => left.saturating_mul(right),
+            Operator::Divide => match left.checked_div(right) {
+                None => 0,
+                Some(quotient) if left % right != 0 && (left < 0) != (right < 0) => quotient - 1,
+                Some(quotient) => quotient,
+            },
+        }
+    }
+}
+
+fn negate_terms(terms: Vec<(Die, i32)>) -> Vec<(Die, i32)> {
+    terms
+        .into_iter()
+        .map(|(die, n)| (die, n.saturating_neg()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::{Explosion, Reroll};
+
+    #[test]
+    fn test_divide_rounds_down() {
+        assert_eq!(Operator::Divide.apply(7, 2), 3);
+        assert_eq!(Operator::Divide.apply(-7, 2), -4);
+        assert_eq!(Operator::Divide.apply(6, -3), -2);
+        assert_eq!(Operator::Divide.apply(1, 0), 0);
+    }
+
+    #[test]
+    fn test_constant_value() {
+        let expression = Expression::Binary(
+            Operator::Multiply,
+            Box::new(Expression::Negate(Box::new(Expression::Constant(2)))),
+            Box::new(Expression::Constant(5)),
+        );
+
+        assert_eq!(expression.constant_value(), Some(-10));
+    }
+
+    #[test]
+    fn test_constant_value_negation_saturates() {
+        let expression = Expression::Negate(Box::new(Expression::Constant(i32::MIN)));
+
+        assert_eq!(expression.constant_value(), Some(i32::MAX));
+    }
+
+    #[test]
+    fn test_max_rolls() {
+        let expression = Expression::Binary(
+            Operator::Subtract,
+            Box::new(Expression::Dice(DiceTerm {
+                explosion: Some(Explosion {
+                    compounding: false,
+                    condition: Comparison::AtLeast(6),
+                }),
+                reroll: Some(Reroll {
+                    once: true,
+                    condition: Comparison::Equal(1),
+                }),
+                ..DiceTerm::new(2, Die::D6)
+            })),
+            Box::new(Expression::Negate(Box::new(Expression::Dice(
+                DiceTerm::new(3, Die::D8),
+            )))),
+        );
+
+        assert_eq!(expression.max_rolls(), 2 * 101 * 2 + 3);
+        assert_eq!(Expression::Constant(4).max_rolls(), 0);
+    }
+
+    #[test]
+    fn test_terms_of_negated_dice() {
+        let expression = Expression::Negate(Box::new(Expression::Dice(DiceTerm::new(3, Die::D8))));
+
+        assert_eq!(expression.terms(), Ok(vec![(Die::D8, -3)]));
+    }
+
+    #[test]
+    fn test_terms_reject_selection() {
+        let expression = Expression::Dice(DiceTerm {
+            selection: Some(Selection::KeepHighest(3)),
+            ..DiceTerm::new(4, Die::D6)
+        });
+
+        assert!(expression.terms().is_err());
+    }
+
+    #[test]
+    fn test_comparison_matches() {
+        assert!(Comparison::Equal(1).matches(1));
+        assert!(!Comparison::Equal(1).matches(2));
+        assert!(Comparison::AtMost(2).matches(2));
+        assert!(Comparison::AtLeast(5).matches(6));
+        assert!(Comparison::AtLeast(1).matches_every_face(6));
+        assert!(!Comparison::AtMost(5).matches_every_face(6));
+    }
+
+    #[test]
+    fn test_success_target_outcome() {
+        let target = SuccessTarget {
+            success: Comparison::AtLeast(5),
+            failure: Some(Comparison::Equal(1)),
+        };
+
+        assert_eq!(target.outcome(6), Some(Outcome::Success));
+        assert_eq!(target.outcome(1), Some(Outcome::Failure));
+        assert_eq!(target.outcome(3), None);
+        assert_eq!(target.score(6), 1);
+        assert_eq!(target.score(1), -1);
+        assert_eq!(target.score(3), 0);
+    }
+
+    #[test]
+    fn test_selection_kept() {
+        assert_eq!(Selection::KeepHighest(3).kept(4), (3, true));
+        assert_eq!(Selection::KeepLowest(1).kept(2), (1, false));
+        assert_eq!(Selection::DropHighest(1).kept(4), (3, false));
+        assert_eq!(Selection::DropLowest(5).kept(4), (0, true));
+    }
+}
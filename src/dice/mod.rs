@@ -1,26 +1,74 @@
+pub mod dice_distribution_calculator;
+pub mod dice_expression_lexer;
 pub mod dice_expression_parser;
 pub mod dice_roller;
+pub mod die;
 pub mod die_roller;
+pub mod distribution;
+pub mod expression;
+pub mod roll;
+#[cfg(test)]
+pub mod sequence_die_roller;
 
-use enum_iterator::Sequence;
+use crate::macros::MacroStore;
 use serde::Serialize;
-use strum_macros::Display;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
 pub trait DieRoller {
     fn roll(&self, die: &Die) -> i32;
+    fn seeded(&self, seed: u64) -> Arc<dyn DieRoller + Send + Sync>;
+    fn seeded_stream(&self, seed: u64, stream: u64) -> Arc<dyn DieRoller + Send + Sync>;
 }
 
 pub trait DiceRoller {
     fn roll(&self, dice: &[(Die, i32)]) -> (Vec<Roll>, i32);
+    fn roll_expression(&self, expression: &Expression) -> RollResult;
+    fn seeded(&self, seed: u64) -> Arc<dyn DiceRoller + Send + Sync>;
+    fn seeded_stream(&self, seed: u64, stream: u64) -> Arc<dyn DiceRoller + Send + Sync>;
+}
+
+pub trait DiceDistributionCalculator {
+    fn distribution(&self, expression: &Expression) -> Result<Distribution, String>;
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Distribution {
+    pub probabilities: BTreeMap<i32, f64>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RollResult {
+    pub rolls: Vec<Roll>,
+    pub total: i32,
+    pub successes: Option<i32>,
 }
 
 #[derive(Debug, Serialize, PartialEq, Clone)]
 pub struct Roll {
     pub die: Die,
     pub value: i32,
+    pub kept: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rerolled: Vec<i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub compounded: Vec<i32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub exploded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum Outcome {
+    #[serde(rename = "success")]
+    Success,
+    #[serde(rename = "failure")]
+    Failure,
 }
 
-#[derive(Debug, Serialize, PartialEq, Clone, Sequence, Display)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum Die {
     D4,
     D6,
@@ -28,9 +76,102 @@ pub enum Die {
     D10,
     D12,
     D20,
+    D100,
     Raw,
+    Custom(u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    Constant(i32),
+    Dice(DiceTerm),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DiceTerm {
+    pub count: i32,
+    pub die: Die,
+    pub selection: Option<Selection>,
+    pub explosion: Option<Explosion>,
+    pub reroll: Option<Reroll>,
+    pub target: Option<SuccessTarget>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Selection {
+    KeepHighest(usize),
+    KeepLowest(usize),
+    DropHighest(usize),
+    DropLowest(usize),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Explosion {
+    pub compounding: bool,
+    pub condition: Comparison,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Reroll {
+    pub once: bool,
+    pub condition: Comparison,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SuccessTarget {
+    pub success: Comparison,
+    pub failure: Option<Comparison>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Comparison {
+    Equal(i32),
+    AtMost(i32),
+    AtLeast(i32),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DiceExpressionError {
+    pub expression: String,
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for DiceExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at position {} in \"{}\"",
+            self.message, self.position, self.expression
+        )
+    }
 }
 
 pub trait DiceExpressionParser {
-    fn parse(&self, expression: &str) -> Result<Vec<(Die, i32)>, String>;
+    fn parse_expression(&self, expression: &str) -> Result<Expression, DiceExpressionError>;
+
+    fn parse_macro(
+        &self,
+        _name: &str,
+        expression: &str,
+        _macros: &(dyn MacroStore + Send + Sync),
+    ) -> Result<Expression, DiceExpressionError> {
+        self.parse_expression(expression)
+    }
+
+    fn parse(&self, expression: &str) -> Result<Vec<(Die, i32)>, String> {
+        self.parse_expression(expression)
+            .map_err(|error| error.to_string())
+            .and_then(|parsed_expression| parsed_expression.terms())
+    }
 }
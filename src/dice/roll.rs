@@ -0,0 +1,45 @@
+use crate::dice::{Die, Roll};
+
+impl Roll {
+    pub fn new(die: Die, value: i32) -> Self {
+        Self {
+            die,
+            value,
+            kept: true,
+            rerolled: vec![],
+            compounded: vec![],
+            exploded: false,
+            outcome: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::Outcome;
+
+    #[test]
+    fn test_new_roll_serializes_only_populated_fields() {
+        assert_eq!(
+            serde_json::to_string(&Roll::new(Die::D6, 4)).unwrap(),
+            "{\"die\":\"D6\",\"value\":4,\"kept\":true}"
+        );
+    }
+
+    #[test]
+    fn test_roll_history_serializes() {
+        let roll = Roll {
+            rerolled: vec![1],
+            compounded: vec![6],
+            exploded: true,
+            outcome: Some(Outcome::Success),
+            ..Roll::new(Die::D6, 12)
+        };
+
+        assert_eq!(
+            serde_json::to_string(&roll).unwrap(),
+            "{\"die\":\"D6\",\"value\":12,\"kept\":true,\"rerolled\":[1],\"compounded\":[6],\"exploded\":true,\"outcome\":\"success\"}"
+        );
+    }
+}
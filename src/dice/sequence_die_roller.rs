@@ -0,0 +1,28 @@
+use crate::dice::{Die, DieRoller};
+use std::sync::{Arc, Mutex};
+
+pub struct SequenceDieRoller {
+    values: Mutex<Vec<i32>>,
+}
+
+impl SequenceDieRoller {
+    pub fn new(values: Vec<i32>) -> Self {
+        Self {
+            values: Mutex::new(values),
+        }
+    }
+}
+
+impl DieRoller for SequenceDieRoller {
+    fn roll(&self, _: &Die) -> i32 {
+        self.values.lock().unwrap().remove(0)
+    }
+
+    fn seeded(&self, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+        Arc::new(SequenceDieRoller::new(self.values.lock().unwrap().clone()))
+    }
+
+    fn seeded_stream(&self, seed: u64, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+        self.seeded(seed)
+    }
+}
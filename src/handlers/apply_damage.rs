@@ -0,0 +1,232 @@
+use crate::monsters::{DamageAdjustment, DamageModifiers, DamageType, Monster};
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct ApplyDamageDependencies {
+    pub(crate) monster_map: Arc<HashMap<String, Monster>>,
+}
+
+#[derive(Deserialize)]
+pub struct DamageInstance {
+    #[serde(rename = "damageType")]
+    damage_type: DamageType,
+    total: i32,
+}
+
+#[derive(Deserialize)]
+pub struct ApplyDamageRequest {
+    damage: Vec<DamageInstance>,
+    target: Option<String>,
+    #[serde(flatten)]
+    modifiers: DamageModifiers,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct AdjustedDamage {
+    #[serde(rename = "damageType")]
+    damage_type: DamageType,
+    total: i32,
+    adjusted: i32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    adjustments: Vec<DamageAdjustment>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ApplyDamageResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    damage: Vec<AdjustedDamage>,
+    total: i32,
+    adjusted: i32,
+}
+
+pub async fn apply_damage(
+    State(dependencies): State<ApplyDamageDependencies>,
+    Json(request): Json<ApplyDamageRequest>,
+) -> Result<Json<ApplyDamageResponse>, (StatusCode, String)> {
+    if request.damage.iter().any(|instance| instance.total < 0) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Damage totals must not be negative".to_string(),
+        ));
+    }
+    let target = match &request.target {
+        None => None,
+        Some(target) => match dependencies.monster_map.get(&target.to_lowercase()) {
+            None => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    format!("Monster `{target}` not found"),
+                ));
+            }
+            Some(monster) => Some(monster),
+        },
+    };
+    let modifiers = match target {
+        None => request.modifiers,
+        Some(monster) => monster.damage_modifiers.merge(&request.modifiers),
+    };
+    let damage = request
+        .damage
+        .into_iter()
+        .map(|instance| {
+            let (adjusted, adjustments) = modifiers.apply(&instance.damage_type, instance.total);
+            AdjustedDamage {
+                damage_type: instance.damage_type,
+                total: instance.total,
+                adjusted,
+                adjustments,
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(Json(ApplyDamageResponse {
+        target: target.map(|monster| monster.name.clone()),
+        total: damage
+            .iter()
+            .fold(0, |total, instance| total.saturating_add(instance.total)),
+        adjusted: damage.iter().fold(0, |adjusted, instance| {
+            adjusted.saturating_add(instance.adjusted)
+        }),
+        damage,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::index::vec_to_map;
+    use crate::utilities::load_from_json::load_from_json;
+
+    fn get_dependencies() -> ApplyDamageDependencies {
+        let monsters = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH);
+        ApplyDamageDependencies {
+            monster_map: Arc::new(vec_to_map(&monsters, |monster| monster.name.to_lowercase())),
+        }
+    }
+
+    fn request(target: Option<&str>, modifiers: DamageModifiers) -> ApplyDamageRequest {
+        ApplyDamageRequest {
+            damage: vec![
+                DamageInstance {
+                    damage_type: DamageType::Fire,
+                    total: 11,
+                },
+                DamageInstance {
+                    damage_type: DamageType::Poison,
+                    total: 6,
+                },
+                DamageInstance {
+                    damage_type: DamageType::Slashing,
+                    total: 5,
+                },
+            ],
+            target: target.map(|target| target.to_string()),
+            modifiers,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_damage_explicit_modifiers() {
+        let result = apply_damage(
+            State(get_dependencies()),
+            Json(request(
+                None,
+                DamageModifiers {
+                    vulnerabilities: vec![DamageType::Slashing],
+                    ..DamageModifiers::default()
+                },
+            )),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.target, None);
+        assert_eq!(result.total, 22);
+        assert_eq!(result.adjusted, 27);
+        assert_eq!(
+            result.damage[2].adjustments,
+            vec![DamageAdjustment::Vulnerability]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_damage_target_monster() {
+        let result = apply_damage(
+            State(get_dependencies()),
+            Json(request(
+                Some("fire elemental"),
+                DamageModifiers {
+                    vulnerabilities: vec![DamageType::Slashing],
+                    ..DamageModifiers::default()
+                },
+            )),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.target, Some("Fire Elemental".to_string()));
+        assert_eq!(
+            result
+                .damage
+                .iter()
+                .map(|instance| instance.adjusted)
+                .collect::<Vec<_>>(),
+            vec![0, 0, 10]
+        );
+        assert_eq!(
+            result.damage[0].adjustments,
+            vec![DamageAdjustment::Immunity]
+        );
+        assert_eq!(result.adjusted, 10);
+    }
+
+    #[tokio::test]
+    async fn test_apply_damage_rejects_negative_totals() {
+        let mut request = request(None, DamageModifiers::default());
+        request.damage[1].total = -6;
+
+        let result = apply_damage(State(get_dependencies()), Json(request)).await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_apply_damage_saturates() {
+        let mut request = request(
+            None,
+            DamageModifiers {
+                vulnerabilities: vec![DamageType::Fire],
+                ..DamageModifiers::default()
+            },
+        );
+        request.damage[0].total = i32::MAX;
+
+        let result = apply_damage(State(get_dependencies()), Json(request))
+            .await
+            .unwrap()
+            .0;
+
+        assert_eq!(result.damage[0].adjusted, i32::MAX);
+        assert_eq!(result.total, i32::MAX);
+        assert_eq!(result.adjusted, i32::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_apply_damage_unknown_target() {
+        let result = apply_damage(
+            State(get_dependencies()),
+            Json(request(Some("Not A Monster"), DamageModifiers::default())),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+}
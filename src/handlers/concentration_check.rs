@@ -0,0 +1,280 @@
+use crate::dice::Roll;
+use crate::handlers::MonsterRollerDependencies;
+use crate::handlers::roll_stat::{
+    RollStatQueryKeys, find_monster, resolve_options, roll_monster_stat,
+};
+use crate::stats::{AdvantageSources, AdvantageType, DifficultyCheck, KeptRoll, StatType};
+use axum::Json;
+use axum::extract::{OriginalUri, Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const MIN_CONCENTRATION_DC: i32 = 10;
+const MAX_DAMAGE_INSTANCES: usize = 100;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum ConcentrationDamage {
+    Single(i32),
+    Multiple(Vec<i32>),
+}
+
+#[derive(Deserialize)]
+pub struct ConcentrationCheckRequest {
+    damage: ConcentrationDamage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConcentrationSave {
+    damage: i32,
+    dc: i32,
+    #[serde(rename = "firstRoll")]
+    first_roll: Vec<Roll>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "secondRoll")]
+    second_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "thirdRoll")]
+    third_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "bonusRoll")]
+    bonus_roll: Option<Vec<Roll>>,
+    result: i32,
+    kept: KeptRoll,
+    natural: i32,
+    passed: bool,
+    margin: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConcentrationCheckResponse {
+    monster: String,
+    saves: Vec<ConcentrationSave>,
+    maintained: bool,
+    #[serde(rename = "advantageSources")]
+    advantage_sources: AdvantageSources,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "rolledWith")]
+    rolled_with: Option<AdvantageType>,
+    seed: u64,
+}
+
+pub async fn concentration_check(
+    Path(monster_name): Path<String>,
+    Query(params): Query<HashMap<RollStatQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
+    State(dependencies): State<MonsterRollerDependencies<StatType>>,
+    Json(request): Json<ConcentrationCheckRequest>,
+) -> Result<Json<ConcentrationCheckResponse>, (StatusCode, String)> {
+    let options = resolve_options(&params, dependencies.dice_expression_parser.as_ref())?;
+    let damage = match request.damage {
+        ConcentrationDamage::Single(damage) => vec![damage],
+        ConcentrationDamage::Multiple(damage) => damage,
+    };
+    if !(1..=MAX_DAMAGE_INSTANCES).contains(&damage.len()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Between 1 and {MAX_DAMAGE_INSTANCES} damage instances are required"),
+        ));
+    }
+    if damage.iter().any(|damage| *damage < 0) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Damage cannot be negative".to_string(),
+        ));
+    }
+    let monster = find_monster(&dependencies, &monster_name)?;
+    let stats_roller = dependencies.stats_roller.seeded(options.seed);
+    let mut saves = vec![];
+    for damage in damage {
+        let dc = concentration_dc(damage);
+        let rolls = roll_monster_stat(
+            &dependencies,
+            stats_roller.as_ref(),
+            monster,
+            &StatType::Constitution,
+            &options,
+            uri.path(),
+        )?;
+        let check = DifficultyCheck::against(rolls.result, dc);
+        saves.push(ConcentrationSave {
+            damage,
+            dc,
+            first_roll: rolls.first_roll,
+            second_roll: rolls.second_roll,
+            third_roll: rolls.third_roll,
+            bonus_roll: rolls.bonus_roll,
+            result: rolls.result,
+            kept: rolls.kept,
+            natural: rolls.natural,
+            passed: check.passed,
+            margin: check.margin,
+        });
+        if !check.passed {
+            break;
+        }
+    }
+    Ok(Json(ConcentrationCheckResponse {
+        monster: monster.name.clone(),
+        maintained: saves.iter().all(|save| save.passed),
+        saves,
+        advantage_sources: options.advantage_sources,
+        rolled_with: options.advantage,
+        seed: options.seed,
+    }))
+}
+
+fn concentration_dc(damage: i32) -> i32 {
+    (damage / 2).max(MIN_CONCENTRATION_DC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+    use crate::dice::dice_roller::DiceRollerImpl;
+    use crate::dice::sequence_die_roller::SequenceDieRoller;
+    use crate::history::roll_journal::RollJournalImpl;
+    use crate::history::{HistoryFilter, RollKind};
+    use crate::monsters::Monster;
+    use crate::stats::modifier_extractor::build_saving_throw_modifier_extractor;
+    use crate::stats::stat_roller::StatRollerImpl;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::index::vec_to_map;
+    use crate::utilities::load_from_json::load_from_json;
+    use axum::http::Uri;
+    use std::sync::Arc;
+
+    fn get_dependencies(values: Vec<i32>) -> MonsterRollerDependencies<StatType> {
+        let monsters = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH);
+        MonsterRollerDependencies {
+            monster_map: Arc::new(vec_to_map(&monsters, |monster| monster.name.to_lowercase())),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(SequenceDieRoller::new(values)),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: Arc::new(build_saving_throw_modifier_extractor()),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        }
+    }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static("/v1/monsters/lich/concentration"))
+    }
+
+    fn request(damage: ConcentrationDamage) -> Json<ConcentrationCheckRequest> {
+        Json(ConcentrationCheckRequest { damage })
+    }
+
+    #[test]
+    fn test_concentration_dc() {
+        assert_eq!(concentration_dc(0), 10);
+        assert_eq!(concentration_dc(21), 10);
+        assert_eq!(concentration_dc(22), 11);
+        assert_eq!(concentration_dc(45), 22);
+    }
+
+    #[tokio::test]
+    async fn test_concentration_check_single() {
+        let dependencies = get_dependencies(vec![1]);
+
+        let result = concentration_check(
+            Path("Lich".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+            request(ConcentrationDamage::Single(30)),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.saves.len(), 1);
+        assert_eq!(result.saves[0].dc, 15);
+        assert_eq!(result.saves[0].result, 11);
+        assert!(!result.maintained);
+        assert_eq!(
+            dependencies.roll_journal.entries(&HistoryFilter::default())[0]
+                .record
+                .expression,
+            "1d20+10"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concentration_check_stops_at_first_failure() {
+        let result = concentration_check(
+            Path("lich".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies(vec![5, 2, 20])),
+            request(ConcentrationDamage::Multiple(vec![8, 40, 12])),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(
+            result
+                .saves
+                .iter()
+                .map(|save| (save.damage, save.dc, save.passed))
+                .collect::<Vec<_>>(),
+            vec![(8, 10, true), (40, 20, false)]
+        );
+        assert!(!result.maintained);
+    }
+
+    #[tokio::test]
+    async fn test_concentration_check_maintained() {
+        let result = concentration_check(
+            Path("lich".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies(vec![5, 10])),
+            request(ConcentrationDamage::Multiple(vec![8, 12])),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.saves.len(), 2);
+        assert!(result.maintained);
+    }
+
+    #[tokio::test]
+    async fn test_concentration_check_invalid_damage() {
+        for damage in [
+            ConcentrationDamage::Multiple(vec![]),
+            ConcentrationDamage::Single(-1),
+            ConcentrationDamage::Multiple(vec![0; MAX_DAMAGE_INSTANCES + 1]),
+        ] {
+            let result = concentration_check(
+                Path("lich".to_string()),
+                Query(HashMap::new()),
+                uri(),
+                State(get_dependencies(vec![])),
+                request(damage),
+            )
+            .await;
+
+            assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concentration_check_unknown_monster() {
+        let result = concentration_check(
+            Path("Not A Monster".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies(vec![10])),
+            request(ConcentrationDamage::Single(10)),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+}
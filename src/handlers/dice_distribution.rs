@@ -0,0 +1,208 @@
+use crate::dice::{DiceDistributionCalculator, DiceExpressionParser};
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+const PERCENTILES: [u32; 7] = [5, 10, 25, 50, 75, 90, 95];
+
+#[derive(Clone)]
+pub struct DiceDistributionDependencies {
+    pub(crate) dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
+    pub(crate) dice_distribution_calculator: Arc<dyn DiceDistributionCalculator + Send + Sync>,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum DiceDistributionQueryKeys {
+    #[serde(rename = "threshold")]
+    Threshold,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ThresholdProbability {
+    value: i32,
+    #[serde(rename = "probabilityAtLeast")]
+    probability_at_least: f64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TotalProbability {
+    total: i32,
+    probability: f64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DiceDistributionResponse {
+    expression: String,
+    mean: f64,
+    variance: f64,
+    min: i32,
+    max: i32,
+    percentiles: BTreeMap<u32, i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold: Option<ThresholdProbability>,
+    probabilities: Vec<TotalProbability>,
+}
+
+pub async fn dice_distribution(
+    Path(expression): Path<String>,
+    Query(params): Query<HashMap<DiceDistributionQueryKeys, String>>,
+    State(dependencies): State<DiceDistributionDependencies>,
+) -> Result<Json<DiceDistributionResponse>, (StatusCode, String)> {
+    let threshold = match params
+        .get(&DiceDistributionQueryKeys::Threshold)
+        .map(|threshold| threshold.parse::<i32>())
+        .transpose()
+    {
+        Ok(threshold) => threshold,
+        Err(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Threshold must be an integer".to_string(),
+            ));
+        }
+    };
+    let parsed_dice_expression = match dependencies
+        .dice_expression_parser
+        .parse_expression(&expression)
+    {
+        Ok(result) => result,
+        Err(error) => {
+            return Err((StatusCode::BAD_REQUEST, error.to_string()));
+        }
+    };
+    let dice_distribution_calculator = dependencies.dice_distribution_calculator.clone();
+    let distribution = match tokio::task::spawn_blocking(move || {
+        dice_distribution_calculator.distribution(&parsed_dice_expression)
+    })
+    .await
+    {
+        Ok(Ok(distribution)) => distribution,
+        Ok(Err(error)) => {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, error));
+        }
+        Err(error) => {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string()));
+        }
+    };
+    Ok(Json(DiceDistributionResponse {
+        expression,
+        mean: distribution.mean(),
+        variance: distribution.variance(),
+        min: distribution.min().unwrap_or(0),
+        max: distribution.max().unwrap_or(0),
+        percentiles: PERCENTILES
+            .iter()
+            .flat_map(|percent| {
+                distribution
+                    .percentile(*percent)
+                    .map(|total| (*percent, total))
+            })
+            .collect(),
+        threshold: threshold.map(|value| ThresholdProbability {
+            value,
+            probability_at_least: distribution.probability_at_least(value),
+        }),
+        probabilities: distribution
+            .probabilities
+            .iter()
+            .map(|(total, probability)| TotalProbability {
+                total: *total,
+                probability: *probability,
+            })
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::dice_distribution_calculator::DiceDistributionCalculatorImpl;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+
+    fn get_dependencies() -> DiceDistributionDependencies {
+        DiceDistributionDependencies {
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            dice_distribution_calculator: Arc::new(DiceDistributionCalculatorImpl::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dice_distribution() {
+        let result = dice_distribution(
+            Path("2d6".to_string()),
+            Query(HashMap::from([(
+                DiceDistributionQueryKeys::Threshold,
+                "11".to_string(),
+            )])),
+            State(get_dependencies()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.min, 2);
+        assert_eq!(result.max, 12);
+        assert!((result.mean - 7.0).abs() < 1e-9);
+        assert_eq!(result.percentiles.get(&50), Some(&7));
+        assert_eq!(result.probabilities.len(), 11);
+        let threshold = result.threshold.unwrap();
+        assert_eq!(threshold.value, 11);
+        assert!((threshold.probability_at_least - 3.0 / 36.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_dice_distribution_without_threshold() {
+        let result = dice_distribution(
+            Path("1d20+5".to_string()),
+            Query(HashMap::new()),
+            State(get_dependencies()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert!(result.threshold.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dice_distribution_invalid_expression() {
+        let result = dice_distribution(
+            Path("2d6+banana".to_string()),
+            Query(HashMap::new()),
+            State(get_dependencies()),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_dice_distribution_invalid_threshold() {
+        let result = dice_distribution(
+            Path("2d6".to_string()),
+            Query(HashMap::from([(
+                DiceDistributionQueryKeys::Threshold,
+                "high".to_string(),
+            )])),
+            State(get_dependencies()),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_dice_distribution_too_complex() {
+        let result = dice_distribution(
+            Path("10000d1000".to_string()),
+            Query(HashMap::new()),
+            State(get_dependencies()),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
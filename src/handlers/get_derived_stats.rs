@@ -0,0 +1,63 @@
+use crate::derived::{DerivedStats, DerivedStatsCalculator};
+use crate::monsters::Monster;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct DerivedStatsDependencies {
+    pub(crate) monster_map: Arc<HashMap<String, Monster>>,
+    pub(crate) derived_stats_calculator: Arc<dyn DerivedStatsCalculator + Send + Sync>,
+}
+
+pub async fn get_derived_stats(
+    Path(monster_name): Path<String>,
+    State(dependencies): State<DerivedStatsDependencies>,
+) -> Result<Json<DerivedStats>, (StatusCode, String)> {
+    dependencies
+        .monster_map
+        .get(&monster_name.to_lowercase())
+        .map(|monster| Json(dependencies.derived_stats_calculator.derive(monster)))
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Monster `{monster_name}` not found"),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derived::derived_stats_calculator::DerivedStatsCalculatorImpl;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::index::vec_to_map;
+    use crate::utilities::load_from_json::load_from_json;
+
+    fn get_dependencies() -> DerivedStatsDependencies {
+        let monsters = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH);
+        DerivedStatsDependencies {
+            monster_map: Arc::new(vec_to_map(&monsters, |monster| monster.name.to_lowercase())),
+            derived_stats_calculator: Arc::new(DerivedStatsCalculatorImpl::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_derived_stats() {
+        let result = get_derived_stats(Path("Goblin".to_string()), State(get_dependencies()))
+            .await
+            .unwrap()
+            .0;
+
+        assert_eq!(result.proficiency_bonus, 2);
+        assert_eq!(result.initiative, 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_derived_stats_not_found() {
+        let result =
+            get_derived_stats(Path("Not A Monster".to_string()), State(get_dependencies())).await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+}
@@ -26,7 +26,7 @@ pub async fn get_monster(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::monsters::{Challenge, Size, Skills, Speed, Stats};
+    use crate::monsters::{Challenge, DamageModifiers, Size, Skills, Speed, Stats};
     use axum::http::StatusCode;
     use std::collections::HashMap;
 
@@ -103,6 +103,7 @@ mod tests {
             },
             image_url: "https://example.com/monster.jpg".to_string(),
             reactions: vec![],
+            damage_modifiers: DamageModifiers::default(),
         };
         monster_map.insert("test_monster".to_string(), monster.clone());
         let dependencies = GetMonsterDependencies {
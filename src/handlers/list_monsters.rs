@@ -1,11 +1,17 @@
-use crate::monsters::Monster;
+use crate::monsters::challenge::parse_rating;
 use crate::monsters::search::MonsterSearch;
+use crate::monsters::{Monster, MonsterFilter, MonsterSort};
 use axum::Json;
 use axum::extract::{Query, State};
-use serde::Deserialize;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
+const TOTAL_COUNT_HEADER: HeaderName = HeaderName::from_static("x-total-count");
+
 #[derive(Clone)]
 pub struct ListMonstersDependencies {
     pub(crate) monsters: Arc<Vec<Monster>>,
@@ -16,18 +22,244 @@ pub struct ListMonstersDependencies {
 pub enum ListMonstersQueryKeys {
     #[serde(rename = "query")]
     Query,
+    #[serde(rename = "minCr")]
+    MinChallenge,
+    #[serde(rename = "maxCr")]
+    MaxChallenge,
+    #[serde(rename = "size")]
+    Size,
+    #[serde(rename = "type")]
+    CreatureType,
+    #[serde(rename = "alignment")]
+    Alignment,
+    #[serde(rename = "speed")]
+    Speed,
+    #[serde(rename = "minAc")]
+    MinAc,
+    #[serde(rename = "maxAc")]
+    MaxAc,
+    #[serde(rename = "minHp")]
+    MinHitPoints,
+    #[serde(rename = "maxHp")]
+    MaxHitPoints,
+    #[serde(rename = "damageType")]
+    DamageType,
+    #[serde(rename = "language")]
+    Language,
+    #[serde(rename = "sort")]
+    Sort,
+    #[serde(rename = "fields")]
+    Fields,
+    #[serde(rename = "offset")]
+    Offset,
+    #[serde(rename = "limit")]
+    Limit,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum MonsterView {
+    Full(Box<Monster>),
+    Fields(Map<String, Value>),
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonsterListing {
+    #[serde(flatten)]
+    monster: MonsterView,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
 }
 
 pub async fn list_monsters(
     Query(params): Query<HashMap<ListMonstersQueryKeys, String>>,
     State(dependencies): State<ListMonstersDependencies>,
-) -> Json<Vec<Monster>> {
+) -> Result<(HeaderMap, Json<Vec<MonsterListing>>), (StatusCode, String)> {
+    let filter = build_filter(&params)?;
+    let sort = parse_value::<MonsterSort>(&params, ListMonstersQueryKeys::Sort)?;
+    let fields = build_fields(
+        params.get(&ListMonstersQueryKeys::Fields),
+        dependencies.monsters.first(),
+    )?;
+    let offset = parse_index(&params, ListMonstersQueryKeys::Offset, "offset")?.unwrap_or(0);
+    let limit = parse_index(&params, ListMonstersQueryKeys::Limit, "limit")?;
+    let search_results;
+    let mut candidates = match params.get(&ListMonstersQueryKeys::Query) {
+        Some(query) => {
+            search_results = dependencies
+                .monster_search
+                .search(query)
+                .map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+            search_results
+                .iter()
+                .map(|result| (&result.monster, Some(result.score)))
+                .collect::<Vec<_>>()
+        }
+        None => dependencies
+            .monsters
+            .iter()
+            .map(|monster| (monster, None))
+            .collect(),
+    };
+    candidates.retain(|(monster, _)| filter.matches(monster));
+    if let Some(sort) = sort {
+        candidates.sort_by(|(first, _), (second, _)| sort.compare(first, second));
+    }
+    let mut headers = HeaderMap::new();
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(candidates.len()));
+    let listings = candidates
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|(monster, score)| project(monster, score, fields.as_deref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((headers, Json(listings)))
+}
+
+fn project(
+    monster: &Monster,
+    score: Option<f64>,
+    fields: Option<&[String]>,
+) -> Result<MonsterListing, (StatusCode, String)> {
+    let monster = match fields {
+        None => MonsterView::Full(Box::new(monster.clone())),
+        Some(fields) => {
+            let mut map = serialize_monster(monster)?;
+            map.retain(|key, _| fields.contains(key));
+            MonsterView::Fields(map)
+        }
+    };
+    Ok(MonsterListing { monster, score })
+}
+
+fn serialize_monster(monster: &Monster) -> Result<Map<String, Value>, (StatusCode, String)> {
+    match serde_json::to_value(monster)
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?
+    {
+        Value::Object(map) => Ok(map),
+        _ => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Monster did not serialize to an object".to_string(),
+        )),
+    }
+}
+
+fn build_fields(
+    fields: Option<&String>,
+    sample: Option<&Monster>,
+) -> Result<Option<Vec<String>>, (StatusCode, String)> {
+    let Some(fields) = fields else {
+        return Ok(None);
+    };
+    let known = match sample {
+        None => Map::new(),
+        Some(monster) => serialize_monster(monster)?,
+    };
+    split_list(Some(fields))
+        .map(|field| {
+            known
+                .keys()
+                .find(|known| known.eq_ignore_ascii_case(field))
+                .cloned()
+                .ok_or((
+                    StatusCode::BAD_REQUEST,
+                    format!("Unknown monster field `{field}`"),
+                ))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+fn build_filter(
+    params: &HashMap<ListMonstersQueryKeys, String>,
+) -> Result<MonsterFilter, (StatusCode, String)> {
+    Ok(MonsterFilter {
+        min_challenge: parse_challenge(params, ListMonstersQueryKeys::MinChallenge)?,
+        max_challenge: parse_challenge(params, ListMonstersQueryKeys::MaxChallenge)?,
+        size: parse_value(params, ListMonstersQueryKeys::Size)?,
+        creature_type: params.get(&ListMonstersQueryKeys::CreatureType).cloned(),
+        alignment: split_list(params.get(&ListMonstersQueryKeys::Alignment))
+            .map(str::to_string)
+            .collect(),
+        movement: split_list(params.get(&ListMonstersQueryKeys::Speed))
+            .map(|mode| mode.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|error| (StatusCode::BAD_REQUEST, error))?,
+        min_ac: parse_number(params, ListMonstersQueryKeys::MinAc, "minAc")?,
+        max_ac: parse_number(params, ListMonstersQueryKeys::MaxAc, "maxAc")?,
+        min_hit_points: parse_number(params, ListMonstersQueryKeys::MinHitPoints, "minHp")?,
+        max_hit_points: parse_number(params, ListMonstersQueryKeys::MaxHitPoints, "maxHp")?,
+        damage_type: parse_value(params, ListMonstersQueryKeys::DamageType)?,
+        language: params.get(&ListMonstersQueryKeys::Language).cloned(),
+    })
+}
+
+fn split_list(value: Option<&String>) -> impl Iterator<Item = &str> {
+    value
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+fn parse_value<T: FromStr<Err = String>>(
+    params: &HashMap<ListMonstersQueryKeys, String>,
+    key: ListMonstersQueryKeys,
+) -> Result<Option<T>, (StatusCode, String)> {
+    params
+        .get(&key)
+        .map(|value| value.parse::<T>())
+        .transpose()
+        .map_err(|error| (StatusCode::BAD_REQUEST, error))
+}
+
+fn parse_challenge(
+    params: &HashMap<ListMonstersQueryKeys, String>,
+    key: ListMonstersQueryKeys,
+) -> Result<Option<f64>, (StatusCode, String)> {
+    params
+        .get(&key)
+        .map(|rating| {
+            parse_rating(rating).ok_or((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid challenge rating `{rating}`"),
+            ))
+        })
+        .transpose()
+}
+
+fn parse_index(
+    params: &HashMap<ListMonstersQueryKeys, String>,
+    key: ListMonstersQueryKeys,
+    name: &str,
+) -> Result<Option<usize>, (StatusCode, String)> {
+    params
+        .get(&key)
+        .map(|value| value.parse::<usize>())
+        .transpose()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("{name} must be a non-negative integer"),
+            )
+        })
+}
+
+fn parse_number(
+    params: &HashMap<ListMonstersQueryKeys, String>,
+    key: ListMonstersQueryKeys,
+    name: &str,
+) -> Result<Option<i32>, (StatusCode, String)> {
     params
-        .get(&ListMonstersQueryKeys::Query)
-        .map(|query| query.split(' ').collect::<Vec<&str>>())
-        .map(|search_terms| dependencies.monster_search.search(&search_terms))
-        .map(Json)
-        .unwrap_or_else(|| Json(dependencies.monsters.to_vec()))
+        .get(&key)
+        .map(|value| value.parse::<i32>())
+        .transpose()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("{name} must be an integer"),
+            )
+        })
 }
 
 #[cfg(test)]
@@ -47,13 +279,28 @@ mod tests {
         }
     }
 
+    fn monster(listing: &MonsterListing) -> Monster {
+        match &listing.monster {
+            MonsterView::Full(monster) => monster.as_ref().clone(),
+            MonsterView::Fields(fields) => {
+                serde_json::from_value(Value::Object(fields.clone())).unwrap()
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_list_monsters_all() {
         let state = get_dependencies();
-        let result = list_monsters(Query(HashMap::new()), State(state)).await.0;
+        let (headers, result) = list_monsters(Query(HashMap::new()), State(state.clone()))
+            .await
+            .unwrap();
 
-        assert_eq!(result.first().unwrap().name, "Aboleth");
-        assert!(result.len() > 1);
+        assert_eq!(monster(&result[0]).name, "Aboleth");
+        assert_eq!(result.len(), state.monsters.len());
+        assert_eq!(
+            headers[TOTAL_COUNT_HEADER],
+            state.monsters.len().to_string()
+        );
     }
 
     #[tokio::test]
@@ -67,9 +314,186 @@ mod tests {
             State(state),
         )
         .await
+        .unwrap()
+        .1
         .0;
 
-        assert!(result.iter().all(|d| d.creature_type == "dragon"));
-        assert!(result.iter().all(|d| d.alignment.contains("evil")));
+        assert!(!result.is_empty());
+        assert!(result.iter().all(|d| d.score.is_some()));
+        assert!(result.iter().all(|d| monster(d).creature_type == "dragon"));
+        assert!(result.iter().all(|d| monster(d).alignment.contains("evil")));
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_query_with_filters() {
+        let state = get_dependencies();
+        let result = list_monsters(
+            Query(HashMap::from([
+                (ListMonstersQueryKeys::Query, "dragon".to_string()),
+                (ListMonstersQueryKeys::MaxChallenge, "4".to_string()),
+                (ListMonstersQueryKeys::Speed, "fly,swim".to_string()),
+                (ListMonstersQueryKeys::Alignment, "evil".to_string()),
+            ])),
+            State(state),
+        )
+        .await
+        .unwrap()
+        .1
+        .0;
+
+        assert!(!result.is_empty());
+        assert!(result.iter().map(monster).all(|monster| {
+            monster.challenge.value().unwrap() <= 4.0
+                && monster.speed.fly > 0
+                && monster.speed.swim > 0
+                && monster.alignment.contains("evil")
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_fractional_challenge() {
+        let state = get_dependencies();
+        let result = list_monsters(
+            Query(HashMap::from([
+                (ListMonstersQueryKeys::MinChallenge, "1/8".to_string()),
+                (ListMonstersQueryKeys::MaxChallenge, "1/8".to_string()),
+                (ListMonstersQueryKeys::Size, "tiny".to_string()),
+            ])),
+            State(state),
+        )
+        .await
+        .unwrap()
+        .1
+        .0;
+
+        assert!(!result.is_empty());
+        assert!(
+            result
+                .iter()
+                .all(|listing| monster(listing).challenge.rating == "1/8")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_invalid_filters() {
+        for (key, value) in [
+            (ListMonstersQueryKeys::MinChallenge, "hard"),
+            (ListMonstersQueryKeys::Size, "colossal"),
+            (ListMonstersQueryKeys::Speed, "fly,teleport"),
+            (ListMonstersQueryKeys::MinAc, "high"),
+            (ListMonstersQueryKeys::DamageType, "sonic"),
+            (ListMonstersQueryKeys::Sort, "speed"),
+            (ListMonstersQueryKeys::Fields, "name,secrets"),
+            (ListMonstersQueryKeys::Offset, "-1"),
+            (ListMonstersQueryKeys::Limit, "ten"),
+            (ListMonstersQueryKeys::Query, "(dragon"),
+        ] {
+            let result = list_monsters(
+                Query(HashMap::from([(key, value.to_string())])),
+                State(get_dependencies()),
+            )
+            .await;
+
+            assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_deeply_nested_query() {
+        for query in [format!("{}dragon", "-".repeat(8_000)), "(".repeat(3_000)] {
+            let result = list_monsters(
+                Query(HashMap::from([(ListMonstersQueryKeys::Query, query)])),
+                State(get_dependencies()),
+            )
+            .await;
+
+            assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_sort_and_paginate() {
+        let state = get_dependencies();
+        let (headers, result) = list_monsters(
+            Query(HashMap::from([
+                (ListMonstersQueryKeys::Sort, "-cr".to_string()),
+                (ListMonstersQueryKeys::Offset, "1".to_string()),
+                (ListMonstersQueryKeys::Limit, "3".to_string()),
+            ])),
+            State(state.clone()),
+        )
+        .await
+        .unwrap();
+        let challenges = result
+            .iter()
+            .map(|listing| monster(listing).challenge.value().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            headers[TOTAL_COUNT_HEADER],
+            state.monsters.len().to_string()
+        );
+        assert_eq!(challenges.len(), 3);
+        assert!(challenges.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(challenges[0] < 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_fields() {
+        let (headers, result) = list_monsters(
+            Query(HashMap::from([
+                (ListMonstersQueryKeys::Query, "goblin".to_string()),
+                (ListMonstersQueryKeys::Fields, "name, CHALLENGE".to_string()),
+                (ListMonstersQueryKeys::Limit, "1".to_string()),
+            ])),
+            State(get_dependencies()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "4");
+        assert_eq!(result.len(), 1);
+        let MonsterView::Fields(fields) = &result[0].monster else {
+            panic!("Expected projected fields");
+        };
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["challenge", "name"]);
+        assert_eq!(fields["name"], "Goblin");
+        assert!(result[0].score.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_without_fields_serializes_whole_monster() {
+        let state = get_dependencies();
+        let (_, result) = list_monsters(
+            Query(HashMap::from([
+                (ListMonstersQueryKeys::Query, "goblin".to_string()),
+                (ListMonstersQueryKeys::Limit, "1".to_string()),
+            ])),
+            State(state.clone()),
+        )
+        .await
+        .unwrap();
+
+        let Value::Object(listing) = serde_json::to_value(&result[0]).unwrap() else {
+            panic!("Expected a listing object");
+        };
+        let mut expected = serialize_monster(&monster(&result[0])).unwrap();
+        expected.insert("score".to_string(), listing["score"].clone());
+        assert!(matches!(result[0].monster, MonsterView::Full(_)));
+        assert_eq!(listing, expected);
+    }
+
+    #[tokio::test]
+    async fn test_list_monsters_unknown_field() {
+        let result = list_monsters(
+            Query(HashMap::from([(
+                ListMonstersQueryKeys::Fields,
+                "name,lair".to_string(),
+            )])),
+            State(get_dependencies()),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
     }
 }
@@ -0,0 +1,177 @@
+use crate::dice::DiceExpressionParser;
+use crate::macros::macro_store::is_valid_macro_name;
+use crate::macros::{Macro, MacroError, MacroStore};
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct MacroDependencies {
+    pub(crate) macro_store: Arc<dyn MacroStore + Send + Sync>,
+    pub(crate) dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
+}
+
+#[derive(Deserialize)]
+pub struct MacroRequest {
+    expression: String,
+}
+
+pub async fn list_macros(State(dependencies): State<MacroDependencies>) -> Json<Vec<Macro>> {
+    Json(dependencies.macro_store.list())
+}
+
+pub async fn get_macro(
+    Path(macro_name): Path<String>,
+    State(dependencies): State<MacroDependencies>,
+) -> Result<Json<Macro>, (StatusCode, String)> {
+    match dependencies.macro_store.get(&macro_name) {
+        None => Err((
+            StatusCode::NOT_FOUND,
+            format!("Macro `{macro_name}` not found"),
+        )),
+        Some(roll_macro) => Ok(Json(roll_macro)),
+    }
+}
+
+pub async fn put_macro(
+    Path(macro_name): Path<String>,
+    State(dependencies): State<MacroDependencies>,
+    Json(request): Json<MacroRequest>,
+) -> Result<Json<Macro>, (StatusCode, String)> {
+    if !is_valid_macro_name(&macro_name) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Macro names may only contain letters, digits and underscores".to_string(),
+        ));
+    }
+    let validate = |roll_macro: &Macro, macros: &(dyn MacroStore + Send + Sync)| {
+        dependencies
+            .dice_expression_parser
+            .parse_macro(&roll_macro.name, &roll_macro.expression, macros)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    };
+    match dependencies.macro_store.save(
+        Macro {
+            name: macro_name,
+            expression: request.expression,
+        },
+        &validate,
+    ) {
+        Ok(roll_macro) => Ok(Json(roll_macro)),
+        Err(MacroError::Invalid(error)) => Err((StatusCode::BAD_REQUEST, error)),
+        Err(MacroError::Storage(error)) => Err((StatusCode::INTERNAL_SERVER_ERROR, error)),
+    }
+}
+
+pub async fn delete_macro(
+    Path(macro_name): Path<String>,
+    State(dependencies): State<MacroDependencies>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match dependencies.macro_store.delete(&macro_name) {
+        Ok(Some(_)) => Ok(StatusCode::NO_CONTENT),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("Macro `{macro_name}` not found"),
+        )),
+        Err(error) => Err((StatusCode::INTERNAL_SERVER_ERROR, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+    use crate::macros::macro_store::MacroStoreImpl;
+    use std::env::temp_dir;
+
+    fn get_dependencies(name: &str) -> MacroDependencies {
+        let path = temp_dir().join(format!("dm-tool-macro-handlers-{name}.json"));
+        let _ = std::fs::remove_file(&path);
+        let macro_store = Arc::new(MacroStoreImpl::load(&path.to_string_lossy()));
+        MacroDependencies {
+            macro_store: macro_store.clone(),
+            dice_expression_parser: Arc::new(
+                DiceExpressionParserImpl::default().with_macros(macro_store),
+            ),
+        }
+    }
+
+    async fn put(dependencies: &MacroDependencies, name: &str, expression: &str) -> StatusCode {
+        match put_macro(
+            Path(name.to_string()),
+            State(dependencies.clone()),
+            Json(MacroRequest {
+                expression: expression.to_string(),
+            }),
+        )
+        .await
+        {
+            Ok(_) => StatusCode::OK,
+            Err((status, _)) => status,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_macro_lifecycle() {
+        let dependencies = get_dependencies("lifecycle");
+
+        assert_eq!(
+            put(&dependencies, "longsword", "1d20+7").await,
+            StatusCode::OK
+        );
+        assert_eq!(list_macros(State(dependencies.clone())).await.0.len(), 1);
+        let roll_macro = get_macro(Path("longsword".to_string()), State(dependencies.clone()))
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(roll_macro.expression, "1d20+7");
+        assert_eq!(
+            delete_macro(Path("longsword".to_string()), State(dependencies.clone())).await,
+            Ok(StatusCode::NO_CONTENT)
+        );
+        assert_eq!(
+            get_macro(Path("longsword".to_string()), State(dependencies))
+                .await
+                .unwrap_err()
+                .0,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_macro_rejects_invalid_expression() {
+        let dependencies = get_dependencies("invalid");
+
+        assert_eq!(
+            put(&dependencies, "broken", "1d20+banana").await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            put(&dependencies, "unknown", "@missing+1").await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_macro_rejects_recursion() {
+        let dependencies = get_dependencies("recursion");
+
+        assert_eq!(put(&dependencies, "a", "1d4").await, StatusCode::OK);
+        assert_eq!(put(&dependencies, "b", "@a+1").await, StatusCode::OK);
+        assert_eq!(put(&dependencies, "a", "@b").await, StatusCode::BAD_REQUEST);
+        assert_eq!(put(&dependencies, "c", "@c").await, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_put_macro_rejects_invalid_name() {
+        let dependencies = get_dependencies("name");
+
+        assert_eq!(
+            put(&dependencies, "long-sword", "1d8").await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -1,17 +1,34 @@
-use crate::dice::Roll;
+use crate::dice::die_roller::random_seed;
+use crate::dice::{DiceExpressionParser, Expression, Roll};
+use crate::history::{RollJournal, RollKind};
 use crate::monsters::Monster;
-use crate::stats::StatRoller;
 use crate::stats::modifier_extractor::ModifierExtractor;
+use crate::stats::{
+    AdvantageSources, AdvantageType, DEFAULT_CRITICAL_THRESHOLD, DifficultyCheck, KeptRoll,
+    StatRoller,
+};
+use axum::http::StatusCode;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+pub mod apply_damage;
+pub mod concentration_check;
+pub mod dice_distribution;
+pub mod get_derived_stats;
 pub mod get_monster;
 pub mod list_dice;
 pub mod list_monsters;
+pub mod manage_macros;
+pub mod resolve_attack;
 pub mod roll_attack;
 pub mod roll_dice;
+pub mod roll_dice_batch;
+pub mod roll_group;
+pub mod roll_history;
 pub mod roll_stat;
+pub mod suggest_monsters;
+pub mod validate_monsters;
 
 #[derive(Serialize, Debug)]
 pub struct StatRollResponse {
@@ -20,12 +37,135 @@ pub struct StatRollResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "secondRoll")]
     second_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "thirdRoll")]
+    third_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "bonusRoll")]
+    bonus_roll: Option<Vec<Roll>>,
     result: i32,
+    kept: KeptRoll,
+    natural: i32,
+    #[serde(rename = "isCriticalSuccess")]
+    is_critical_success: bool,
+    #[serde(rename = "isCriticalFailure")]
+    is_critical_failure: bool,
+    #[serde(flatten)]
+    check: Option<DifficultyCheck>,
+    #[serde(rename = "advantageSources")]
+    advantage_sources: AdvantageSources,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "rolledWith")]
+    rolled_with: Option<AdvantageType>,
+    seed: u64,
 }
 
 #[derive(Clone)]
 pub struct MonsterRollerDependencies<T> {
     pub(crate) monster_map: Arc<HashMap<String, Monster>>,
     pub(crate) stats_roller: Arc<dyn StatRoller + Sync + Send>,
+    pub(crate) dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
     pub(crate) modifier_extractor: Arc<ModifierExtractor<T>>,
+    pub(crate) roll_journal: Arc<dyn RollJournal + Send + Sync>,
+    pub(crate) roll_kind: RollKind,
+}
+
+pub(crate) fn resolve_seed(seed: Option<&String>) -> Result<u64, (StatusCode, String)> {
+    match seed {
+        None => Ok(random_seed()),
+        Some(seed) => seed.parse::<u64>().map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                "Seed must be a non-negative integer".to_string(),
+            )
+        }),
+    }
+}
+
+pub(crate) fn resolve_critical_threshold(
+    critical_range: Option<&String>,
+) -> Result<i32, (StatusCode, String)> {
+    match critical_range {
+        None => Ok(DEFAULT_CRITICAL_THRESHOLD),
+        Some(critical_range) => critical_range
+            .parse::<i32>()
+            .ok()
+            .filter(|threshold| (2..=20).contains(threshold))
+            .ok_or((
+                StatusCode::BAD_REQUEST,
+                "Crit range must be an integer between 2 and 20".to_string(),
+            )),
+    }
+}
+
+pub(crate) fn resolve_difficulty_class(
+    difficulty_class: Option<&String>,
+) -> Result<Option<i32>, (StatusCode, String)> {
+    difficulty_class
+        .map(|difficulty_class| difficulty_class.parse::<i32>())
+        .transpose()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "DC must be an integer".to_string()))
+}
+
+pub(crate) fn resolve_flag(
+    flag: Option<&String>,
+    name: &str,
+) -> Result<bool, (StatusCode, String)> {
+    match flag.map(|flag| flag.to_lowercase()).as_deref() {
+        None | Some("false") => Ok(false),
+        Some("") | Some("true") => Ok(true),
+        Some(_) => Err((
+            StatusCode::BAD_REQUEST,
+            format!("{name} must be empty, true or false"),
+        )),
+    }
+}
+
+pub(crate) fn resolve_advantage_sources(
+    advantage: Option<&String>,
+    disadvantage: Option<&String>,
+    elven_accuracy: bool,
+) -> AdvantageSources {
+    AdvantageSources {
+        advantage: named_sources(advantage, "advantage"),
+        disadvantage: named_sources(disadvantage, "disadvantage"),
+        elven_accuracy,
+    }
+}
+
+fn named_sources(sources: Option<&String>, unnamed: &str) -> Vec<String> {
+    let Some(sources) = sources else {
+        return vec![];
+    };
+    let named = sources
+        .split(',')
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty())
+        .collect::<Vec<_>>();
+    match named.is_empty() {
+        true => vec![unnamed.to_string()],
+        false => named,
+    }
+}
+
+pub(crate) fn resolve_bonus(
+    dice_expression_parser: &(dyn DiceExpressionParser + Send + Sync),
+    bonus: Option<&String>,
+) -> Result<Option<(String, Expression)>, (StatusCode, String)> {
+    bonus
+        .map(|bonus| {
+            dice_expression_parser
+                .parse_expression(bonus)
+                .map(|expression| (bonus_suffix(bonus), expression))
+                .map_err(|error| (StatusCode::BAD_REQUEST, format!("Invalid bonus: {error}")))
+        })
+        .transpose()
+}
+
+fn bonus_suffix(bonus: &str) -> String {
+    let bonus = bonus.trim();
+    match bonus.starts_with(['+', '-']) {
+        true => bonus.to_string(),
+        false => format!("+{bonus}"),
+    }
 }
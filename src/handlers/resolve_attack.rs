@@ -0,0 +1,639 @@
+use crate::criticals::{CriticalRule, CriticalStrategy};
+use crate::dice::{DiceExpressionParser, DiceRoller, Roll};
+use crate::handlers::roll_attack::{Damage, record_damage, resolve_critical_damage, roll_damage};
+use crate::handlers::roll_stat::{d20_expression, recorded_rolls};
+use crate::handlers::{
+    resolve_advantage_sources, resolve_bonus, resolve_critical_threshold, resolve_flag,
+    resolve_seed,
+};
+use crate::history::{RollJournal, RollKind, RollRecord};
+use crate::monsters::Monster;
+use crate::stats::{AdvantageSources, AdvantageType, AttackOutcome, KeptRoll, StatRoller};
+use axum::Json;
+use axum::extract::{OriginalUri, Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const DAMAGE_STREAM: u64 = 1;
+
+#[derive(Clone)]
+pub struct ResolveAttackDependencies {
+    pub(crate) monster_map: Arc<HashMap<String, Monster>>,
+    pub(crate) stats_roller: Arc<dyn StatRoller + Send + Sync>,
+    pub(crate) dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
+    pub(crate) dice_roller: Arc<dyn DiceRoller + Send + Sync>,
+    pub(crate) roll_journal: Arc<dyn RollJournal + Send + Sync>,
+    pub(crate) critical_strategies:
+        Arc<HashMap<CriticalRule, Arc<dyn CriticalStrategy + Send + Sync>>>,
+    pub(crate) critical_rule: CriticalRule,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum ResolveAttackQueryKeys {
+    #[serde(rename = "ac")]
+    ArmorClass,
+    #[serde(rename = "advantage")]
+    Advantage,
+    #[serde(rename = "disadvantage")]
+    Disadvantage,
+    #[serde(rename = "elvenAccuracy")]
+    ElvenAccuracy,
+    #[serde(rename = "seed")]
+    Seed,
+    #[serde(rename = "critRange")]
+    CriticalRange,
+    #[serde(rename = "critRule")]
+    CriticalRule,
+    #[serde(rename = "extraDice")]
+    ExtraDice,
+    #[serde(rename = "bonus")]
+    Bonus,
+}
+
+#[derive(Serialize)]
+pub struct ResolveAttackResponse {
+    attack: String,
+    #[serde(rename = "targetAc")]
+    target_ac: i32,
+    #[serde(rename = "firstRoll")]
+    first_roll: Vec<Roll>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "secondRoll")]
+    second_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "thirdRoll")]
+    third_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "bonusRoll")]
+    bonus_roll: Option<Vec<Roll>>,
+    kept: KeptRoll,
+    natural: i32,
+    #[serde(rename = "attackTotal")]
+    attack_total: i32,
+    outcome: AttackOutcome,
+    hit: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "damageRolls")]
+    damage_rolls: Vec<Damage>,
+    damage: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "criticalRule")]
+    critical_rule: Option<CriticalRule>,
+    #[serde(rename = "advantageSources")]
+    advantage_sources: AdvantageSources,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "rolledWith")]
+    rolled_with: Option<AdvantageType>,
+    seed: u64,
+}
+
+pub async fn resolve_attack(
+    Path((monster_name, attack_index)): Path<(String, usize)>,
+    Query(params): Query<HashMap<ResolveAttackQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
+    State(dependencies): State<ResolveAttackDependencies>,
+) -> Result<Json<ResolveAttackResponse>, (StatusCode, String)> {
+    let seed = resolve_seed(params.get(&ResolveAttackQueryKeys::Seed))?;
+    let critical_threshold =
+        resolve_critical_threshold(params.get(&ResolveAttackQueryKeys::CriticalRange))?;
+    let critical_damage = resolve_critical_damage(
+        params.get(&ResolveAttackQueryKeys::CriticalRule),
+        params.get(&ResolveAttackQueryKeys::ExtraDice),
+        &dependencies.critical_strategies,
+        dependencies.critical_rule,
+    )?;
+    let target_ac = match params
+        .get(&ResolveAttackQueryKeys::ArmorClass)
+        .map(|ac| ac.parse::<i32>())
+    {
+        Some(Ok(target_ac)) => target_ac,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "A target `ac` integer is required".to_string(),
+            ));
+        }
+    };
+    let selected_monster = match dependencies.monster_map.get(&monster_name.to_lowercase()) {
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("Monster `{monster_name}` not found"),
+            ));
+        }
+        Some(monster) => monster,
+    };
+    let attack_roll = match selected_monster
+        .actions
+        .iter()
+        .flat_map(|actions| actions.attack_rolls.get(attack_index))
+        .next()
+    {
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("Attack {attack_index} not found for `{monster_name}`"),
+            ));
+        }
+        Some(attack_roll) => attack_roll,
+    };
+    let advantage_sources = resolve_advantage_sources(
+        params.get(&ResolveAttackQueryKeys::Advantage),
+        params.get(&ResolveAttackQueryKeys::Disadvantage),
+        resolve_flag(
+            params.get(&ResolveAttackQueryKeys::ElvenAccuracy),
+            "elvenAccuracy",
+        )?,
+    );
+    let advantage = advantage_sources.resolve();
+    let bonus = resolve_bonus(
+        dependencies.dice_expression_parser.as_ref(),
+        params.get(&ResolveAttackQueryKeys::Bonus),
+    )?;
+    let to_hit = dependencies.stats_roller.seeded(seed).roll_stat(
+        attack_roll.hit,
+        &advantage.as_ref(),
+        critical_threshold,
+        bonus.as_ref().map(|(_, bonus)| bonus),
+    );
+    let outcome = AttackOutcome::resolve(&to_hit, target_ac);
+    dependencies.roll_journal.record(RollRecord {
+        kind: RollKind::Attack,
+        endpoint: uri.path().to_string(),
+        monster: Some(selected_monster.name.clone()),
+        expression: format!(
+            "{}{:+}{}",
+            d20_expression(&advantage),
+            attack_roll.hit,
+            bonus.as_ref().map_or("", |(suffix, _)| suffix.as_str())
+        ),
+        result: to_hit.result,
+        rolls: recorded_rolls(&to_hit),
+        seed,
+    });
+    let critical = match outcome.is_critical() {
+        true => Some(&critical_damage),
+        false => None,
+    };
+    let (damage_rolls, damage) = match outcome.is_hit() {
+        false => (vec![], 0),
+        true => roll_damage(
+            dependencies.dice_expression_parser.as_ref(),
+            dependencies
+                .dice_roller
+                .seeded_stream(seed, DAMAGE_STREAM)
+                .as_ref(),
+            attack_roll,
+            critical,
+        ),
+    };
+    record_damage(
+        dependencies.roll_journal.as_ref(),
+        uri.path(),
+        selected_monster,
+        attack_roll,
+        &damage_rolls,
+        seed,
+    );
+    Ok(Json(ResolveAttackResponse {
+        attack: attack_roll.name.clone(),
+        target_ac,
+        first_roll: to_hit.first_roll,
+        second_roll: to_hit.second_roll,
+        third_roll: to_hit.third_roll,
+        bonus_roll: to_hit.bonus_roll,
+        kept: to_hit.kept,
+        natural: to_hit.natural,
+        attack_total: to_hit.result,
+        outcome,
+        hit: outcome.is_hit(),
+        damage_rolls,
+        damage,
+        critical_rule: critical.map(|critical| critical.rule),
+        advantage_sources,
+        rolled_with: advantage,
+        seed,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::criticals::critical_strategy::build_critical_strategies;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+    use crate::dice::dice_roller::DiceRollerImpl;
+    use crate::dice::die_roller::DieRollerImpl;
+    use crate::dice::sequence_die_roller::SequenceDieRoller;
+    use crate::dice::{Die, DieRoller};
+    use crate::history::HistoryFilter;
+    use crate::history::roll_journal::RollJournalImpl;
+    use crate::monsters::{
+        Actions, AttackRoll, AttackType, Challenge, DamageModifiers, DamageRoll, DamageType, Size,
+        Skills, Speed, Stats,
+    };
+    use crate::stats::stat_roller::StatRollerImpl;
+    use axum::http::Uri;
+
+    struct MockDieRoller {
+        value: i32,
+    }
+    impl DieRoller for MockDieRoller {
+        fn roll(&self, _: &Die) -> i32 {
+            self.value
+        }
+
+        fn seeded(&self, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+            Arc::new(MockDieRoller { value: self.value })
+        }
+
+        fn seeded_stream(&self, _: u64, _: u64) -> Arc<dyn DieRoller + Send + Sync> {
+            Arc::new(MockDieRoller { value: self.value })
+        }
+    }
+
+    fn get_dependencies(die_roller: Arc<dyn DieRoller + Send + Sync>) -> ResolveAttackDependencies {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test monster".to_string(), get_test_monster());
+        let dice_roller = Arc::new(DiceRollerImpl::new(die_roller));
+        ResolveAttackDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(dice_roller.clone())),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            dice_roller,
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            critical_strategies: Arc::new(build_critical_strategies()),
+            critical_rule: CriticalRule::DoubleDice,
+        }
+    }
+
+    fn query(ac: &str) -> Query<HashMap<ResolveAttackQueryKeys, String>> {
+        Query(HashMap::from([(
+            ResolveAttackQueryKeys::ArmorClass,
+            ac.to_string(),
+        )]))
+    }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static("/v1/monsters/test%20monster/attack/0"))
+    }
+
+    async fn resolve(
+        die_value: i32,
+        ac: &str,
+    ) -> Result<Json<ResolveAttackResponse>, (StatusCode, String)> {
+        resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            query(ac),
+            uri(),
+            State(get_dependencies(Arc::new(MockDieRoller {
+                value: die_value,
+            }))),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_hit() {
+        let result = resolve(10, "15").await.unwrap().0;
+
+        assert_eq!(result.attack, "Claw");
+        assert_eq!(result.natural, 10);
+        assert_eq!(result.attack_total, 15);
+        assert_eq!(result.outcome, AttackOutcome::Hit);
+        assert_eq!(result.damage_rolls.first().unwrap().rolls.len(), 4);
+        assert_eq!(result.damage, 38);
+        assert_eq!(result.critical_rule, None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_miss_rolls_no_damage() {
+        let result = resolve(9, "15").await.unwrap().0;
+
+        assert_eq!(result.outcome, AttackOutcome::Miss);
+        assert!(!result.hit);
+        assert!(result.damage_rolls.is_empty());
+        assert_eq!(result.damage, 0);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_natural_twenty_doubles_dice() {
+        let result = resolve(20, "30").await.unwrap().0;
+
+        assert_eq!(result.outcome, AttackOutcome::CriticalHit);
+        assert_eq!(result.damage_rolls.first().unwrap().rolls.len(), 7);
+        assert_eq!(result.critical_rule, Some(CriticalRule::DoubleDice));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_critical_rule() {
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "30".to_string()),
+                (
+                    ResolveAttackQueryKeys::CriticalRule,
+                    "doubleTotal".to_string(),
+                ),
+                (ResolveAttackQueryKeys::ExtraDice, "2".to_string()),
+            ])),
+            uri(),
+            State(get_dependencies(Arc::new(MockDieRoller { value: 20 }))),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.critical_rule, Some(CriticalRule::DoubleTotal));
+        assert_eq!(result.damage_rolls.first().unwrap().rolls.len(), 6);
+        assert_eq!(result.damage, 176);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_with_bonus_dice() {
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "22".to_string()),
+                (ResolveAttackQueryKeys::Bonus, "-1d4".to_string()),
+            ])),
+            uri(),
+            State(get_dependencies(Arc::new(MockDieRoller { value: 19 }))),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.bonus_roll.unwrap().len(), 1);
+        assert_eq!(result.natural, 19);
+        assert_eq!(result.attack_total, 5);
+        assert_eq!(result.outcome, AttackOutcome::Miss);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_with_crit_range() {
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "30".to_string()),
+                (ResolveAttackQueryKeys::CriticalRange, "19".to_string()),
+            ])),
+            uri(),
+            State(get_dependencies(Arc::new(MockDieRoller { value: 19 }))),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.natural, 19);
+        assert_eq!(result.outcome, AttackOutcome::CriticalHit);
+        assert_eq!(result.damage_rolls.first().unwrap().rolls.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_invalid_crit_range() {
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "10".to_string()),
+                (ResolveAttackQueryKeys::CriticalRange, "1".to_string()),
+            ])),
+            uri(),
+            State(get_dependencies(Arc::new(MockDieRoller { value: 10 }))),
+        )
+        .await;
+
+        assert_eq!(result.err().unwrap().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_invalid_elven_accuracy() {
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "10".to_string()),
+                (ResolveAttackQueryKeys::ElvenAccuracy, "yes".to_string()),
+            ])),
+            uri(),
+            State(get_dependencies(Arc::new(MockDieRoller { value: 10 }))),
+        )
+        .await;
+
+        assert_eq!(result.err().unwrap().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_natural_one_misses() {
+        let result = resolve(1, "1").await.unwrap().0;
+
+        assert_eq!(result.outcome, AttackOutcome::CriticalMiss);
+        assert!(result.damage_rolls.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_with_disadvantage() {
+        let dependencies = get_dependencies(Arc::new(DieRollerImpl::default()));
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "12".to_string()),
+                (ResolveAttackQueryKeys::Disadvantage, "".to_string()),
+            ])),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert!(result.second_roll.is_some());
+        let history = dependencies.roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history.len(), if result.hit { 2 } else { 1 });
+        assert_eq!(history[0].record.kind, RollKind::Attack);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_records_dropped_d20() {
+        let dependencies = get_dependencies(Arc::new(SequenceDieRoller::new(vec![5, 17])));
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (ResolveAttackQueryKeys::ArmorClass, "30".to_string()),
+                (ResolveAttackQueryKeys::Disadvantage, "".to_string()),
+            ])),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.natural, 5);
+        let history = dependencies.roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].record.expression, "2d20kl1+5");
+        let kept = history[0]
+            .record
+            .rolls
+            .iter()
+            .filter(|roll| roll.die == Die::D20)
+            .map(|roll| (roll.value, roll.kept))
+            .collect::<Vec<(i32, bool)>>();
+        assert_eq!(kept, vec![(5, true), (17, false)]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_requires_ac() {
+        assert_eq!(
+            resolve(10, "high").await.err().unwrap().0,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_not_found() {
+        let result = resolve_attack(
+            Path(("test monster".to_string(), 3)),
+            query("10"),
+            uri(),
+            State(get_dependencies(Arc::new(DieRollerImpl::default()))),
+        )
+        .await;
+
+        assert_eq!(result.err().unwrap().0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attack_damage_does_not_repeat_to_hit_roll() {
+        let mut monster = get_test_monster();
+        if let Some(actions) = monster.actions.as_mut() {
+            actions.attack_rolls[0].damage[0].roll = "1d20".to_string();
+        }
+        let mut dependencies = get_dependencies(Arc::new(DieRollerImpl::default()));
+        dependencies.monster_map = Arc::new(HashMap::from([("test monster".to_string(), monster)]));
+        let mut pairs = vec![];
+        let mut naturals = HashMap::new();
+        for seed in 1..=21 {
+            let result = resolve_attack(
+                Path(("test monster".to_string(), 0)),
+                Query(HashMap::from([
+                    (ResolveAttackQueryKeys::ArmorClass, "1".to_string()),
+                    (ResolveAttackQueryKeys::CriticalRange, "20".to_string()),
+                    (ResolveAttackQueryKeys::Seed, seed.to_string()),
+                ])),
+                uri(),
+                State(dependencies.clone()),
+            )
+            .await
+            .unwrap()
+            .0;
+            naturals.insert(seed, result.natural);
+            if let Some(damage) = result.damage_rolls.first() {
+                pairs.push((seed, result.natural, damage.rolls[0].value));
+            }
+        }
+
+        assert!(!pairs.is_empty());
+        assert!(pairs.iter().any(|(_, natural, damage)| natural != damage));
+        assert!(
+            pairs
+                .iter()
+                .any(|(seed, _, damage)| naturals.get(&(seed + 1)) != Some(damage))
+        );
+        let history = dependencies.roll_journal.entries(&HistoryFilter::default());
+        assert!(
+            history
+                .iter()
+                .filter(|entry| entry.record.kind == RollKind::Damage)
+                .all(|entry| (1..=21).contains(&entry.record.seed))
+        );
+    }
+
+    fn get_test_monster() -> Monster {
+        Monster {
+            name: "Test Monster".to_string(),
+            ac: 10,
+            size: Size::Medium,
+            alignment: "Neutral".to_string(),
+            languages: vec!["Common".to_string()],
+            creature_type: "".to_string(),
+            max_hit_points: 100,
+            hit_dice: "10d10".to_string(),
+            speed: Speed {
+                walk: 30,
+                fly: 0,
+                swim: 0,
+                burrow: 0,
+                climb: 0,
+                hover: false,
+            },
+            modifiers: Stats {
+                strength: 10,
+                dexterity: 12,
+                constitution: 14,
+                intelligence: 10,
+                wisdom: 13,
+                charisma: 8,
+            },
+            stats: Stats {
+                strength: 10,
+                dexterity: 12,
+                constitution: 14,
+                intelligence: 10,
+                wisdom: 13,
+                charisma: 8,
+            },
+            saving_throws: Stats {
+                strength: 10,
+                dexterity: 12,
+                constitution: 14,
+                intelligence: 10,
+                wisdom: 13,
+                charisma: 8,
+            },
+            skills: Skills {
+                acrobatics: 5,
+                animal_handling: 5,
+                arcana: 3,
+                athletics: 5,
+                deception: 3,
+                history: 2,
+                insight: 4,
+                intimidation: 4,
+                investigation: 3,
+                medicine: 4,
+                nature: 2,
+                perception: 4,
+                performance: 3,
+                persuasion: 3,
+                religion: 2,
+                sleight_of_hand: 5,
+                stealth: 0,
+                survival: 4,
+            },
+            traits: vec![],
+            actions: Some(Actions {
+                list: vec![],
+                attack_rolls: vec![AttackRoll {
+                    name: "Claw".to_string(),
+                    attack_type: AttackType::MeleeWeapon,
+                    reach: 0,
+                    hit: 5,
+                    damage: vec![DamageRoll {
+                        damage_type: DamageType::Bludgeoning,
+                        roll: "3d6+8".to_string(),
+                    }],
+                }],
+            }),
+            legendary_actions: vec![],
+            challenge: Challenge {
+                rating: "Medium".to_string(),
+                xp: 1000,
+            },
+            image_url: "https://example.com/monster.jpg".to_string(),
+            reactions: vec![],
+            damage_modifiers: DamageModifiers::default(),
+        }
+    }
+}
@@ -1,26 +1,46 @@
+use crate::criticals::{CriticalRule, CriticalStrategy};
 use crate::dice::{DiceExpressionParser, DiceRoller, Die, Roll};
-use crate::monsters::{DamageType, Monster};
-use crate::stats::Critical;
+use crate::handlers::resolve_seed;
+use crate::history::{RollJournal, RollKind, RollRecord};
+use crate::monsters::{AttackRoll, DamageType, Monster};
 use axum::Json;
-use axum::extract::{Path, Query, State};
+use axum::extract::{OriginalUri, Path, Query, State};
 use axum::http::StatusCode;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+const MAX_EXTRA_DICE: i32 = 10;
+
 #[derive(Clone)]
 pub struct RollAttackDependencies {
     pub(crate) dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
     pub(crate) dice_roller: Arc<dyn DiceRoller + Send + Sync>,
     pub(crate) monster_map: Arc<HashMap<String, Monster>>,
+    pub(crate) roll_journal: Arc<dyn RollJournal + Send + Sync>,
+    pub(crate) critical_strategies:
+        Arc<HashMap<CriticalRule, Arc<dyn CriticalStrategy + Send + Sync>>>,
+    pub(crate) critical_rule: CriticalRule,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum RollAttackQueryKeys {
+    #[serde(rename = "critical")]
+    Critical,
+    #[serde(rename = "critRule")]
+    CriticalRule,
+    #[serde(rename = "extraDice")]
+    ExtraDice,
+    #[serde(rename = "seed")]
+    Seed,
 }
 
 #[derive(Serialize)]
 pub struct Damage {
-    total: i32,
-    rolls: Vec<Roll>,
+    pub(crate) total: i32,
+    pub(crate) rolls: Vec<Roll>,
     #[serde(rename = "damageType")]
-    damage_type: DamageType,
+    pub(crate) damage_type: DamageType,
 }
 
 #[derive(Serialize)]
@@ -28,13 +48,26 @@ pub struct RollAttackResponse {
     #[serde(rename = "damageRolls")]
     damage_rolls: Vec<Damage>,
     total: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "criticalRule")]
+    critical_rule: Option<CriticalRule>,
+    seed: u64,
+}
+
+pub(crate) struct CriticalDamage {
+    pub(crate) rule: CriticalRule,
+    pub(crate) strategy: Arc<dyn CriticalStrategy + Send + Sync>,
+    pub(crate) extra_dice: i32,
 }
 
 pub async fn roll_attack(
     Path((monster_name, attack_index)): Path<(String, usize)>,
-    Query(critical): Query<HashMap<Critical, String>>,
+    Query(params): Query<HashMap<RollAttackQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
     State(dependencies): State<RollAttackDependencies>,
 ) -> Result<Json<RollAttackResponse>, (StatusCode, String)> {
+    let seed = resolve_seed(params.get(&RollAttackQueryKeys::Seed))?;
+    let dice_roller = dependencies.dice_roller.seeded(seed);
     let selected_monster = match dependencies.monster_map.get(&monster_name.to_lowercase()) {
         None => {
             return Err((
@@ -44,43 +77,124 @@ pub async fn roll_attack(
         }
         Some(monster) => monster,
     };
-    let critical = critical.iter().last().is_some();
-    let damage_rolls = selected_monster
+    let critical_damage = resolve_critical_damage(
+        params.get(&RollAttackQueryKeys::CriticalRule),
+        params.get(&RollAttackQueryKeys::ExtraDice),
+        &dependencies.critical_strategies,
+        dependencies.critical_rule,
+    )?;
+    let critical = match params.contains_key(&RollAttackQueryKeys::Critical) {
+        true => Some(&critical_damage),
+        false => None,
+    };
+    let attack_roll = selected_monster
         .actions
         .iter()
-        .map(|actions| &actions.attack_rolls)
-        .flat_map(|attack_rolls| attack_rolls.get(attack_index))
-        .flat_map(|attack_roll| attack_roll.damage.clone())
+        .flat_map(|actions| actions.attack_rolls.get(attack_index))
+        .next();
+    let (damage_rolls, total) = match attack_roll {
+        None => (vec![], 0),
+        Some(attack_roll) => roll_damage(
+            dependencies.dice_expression_parser.as_ref(),
+            dice_roller.as_ref(),
+            attack_roll,
+            critical,
+        ),
+    };
+    if let Some(attack_roll) = attack_roll {
+        record_damage(
+            dependencies.roll_journal.as_ref(),
+            uri.path(),
+            selected_monster,
+            attack_roll,
+            &damage_rolls,
+            seed,
+        );
+    }
+    Ok(Json(RollAttackResponse {
+        total,
+        damage_rolls,
+        critical_rule: critical.map(|critical| critical.rule),
+        seed,
+    }))
+}
+
+pub(crate) fn resolve_critical_damage(
+    rule: Option<&String>,
+    extra_dice: Option<&String>,
+    critical_strategies: &HashMap<CriticalRule, Arc<dyn CriticalStrategy + Send + Sync>>,
+    default_rule: CriticalRule,
+) -> Result<CriticalDamage, (StatusCode, String)> {
+    let rule = rule
+        .map(|rule| rule.parse::<CriticalRule>())
+        .transpose()
+        .map_err(|error| (StatusCode::BAD_REQUEST, error))?
+        .unwrap_or(default_rule);
+    let strategy = critical_strategies.get(&rule).cloned().ok_or((
+        StatusCode::BAD_REQUEST,
+        format!("Critical rule `{rule:?}` is not available"),
+    ))?;
+    let extra_dice = match extra_dice.map(|extra_dice| extra_dice.parse::<i32>()) {
+        None => 0,
+        Some(Ok(extra_dice)) if (0..=MAX_EXTRA_DICE).contains(&extra_dice) => extra_dice,
+        Some(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Extra dice must be an integer between 0 and {MAX_EXTRA_DICE}"),
+            ));
+        }
+    };
+    Ok(CriticalDamage {
+        rule,
+        strategy,
+        extra_dice,
+    })
+}
+
+pub(crate) fn roll_damage(
+    dice_expression_parser: &(dyn DiceExpressionParser + Send + Sync),
+    dice_roller: &(dyn DiceRoller + Send + Sync),
+    attack_roll: &AttackRoll,
+    critical: Option<&CriticalDamage>,
+) -> (Vec<Damage>, i32) {
+    let damage_rolls = attack_roll
+        .damage
+        .iter()
         .map(|damage_roll| {
             (
-                dependencies.dice_expression_parser.parse(&damage_roll.roll),
-                damage_roll.damage_type,
+                dice_expression_parser.parse(&damage_roll.roll),
+                damage_roll.damage_type.clone(),
             )
         })
         .map(|(rolls, damage_type)| (rolls.unwrap(), damage_type))
-        .map(|(damage_rolls, damage_type)| {
-            let adjusted_damage_rolls = damage_rolls
-                .iter()
-                .map(|damage_roll| {
-                    if !critical {
-                        return damage_roll.clone();
-                    }
-                    let (die, n) = damage_roll;
-                    match die {
-                        Die::Raw => damage_roll.clone(),
-                        die => (die.clone(), n * 2),
+        .enumerate()
+        .map(|(index, (required_rolls, damage_type))| {
+            let (rolls, total) = match critical {
+                None => dice_roller.roll(&required_rolls),
+                Some(critical) => {
+                    let (rolls, total) = critical
+                        .strategy
+                        .roll_critical(dice_roller, &required_rolls);
+                    let extra_die = required_rolls
+                        .iter()
+                        .map(|(die, _)| die)
+                        .filter(|die| **die != Die::Raw)
+                        .max_by_key(|die| die.sides());
+                    match (index, extra_die) {
+                        (0, Some(die)) if critical.extra_dice > 0 => {
+                            let (extra_rolls, extra_total) =
+                                dice_roller.roll(&[(die.clone(), critical.extra_dice)]);
+                            ([rolls, extra_rolls].concat(), total + extra_total)
+                        }
+                        _ => (rolls, total),
                     }
-                })
-                .collect::<Vec<(Die, i32)>>();
-            (adjusted_damage_rolls, damage_type)
-        })
-        .map(|(required_rolls, damage_type)| {
-            (dependencies.dice_roller.roll(&required_rolls), damage_type)
-        })
-        .map(|((rolls, total), damage_type)| Damage {
-            damage_type,
-            rolls,
-            total,
+                }
+            };
+            Damage {
+                damage_type,
+                rolls,
+                total,
+            }
         })
         .collect::<Vec<_>>();
     let total = damage_rolls
@@ -88,21 +202,52 @@ pub async fn roll_attack(
         .map(|roll| roll.total)
         .reduce(|first, second| first + second)
         .unwrap_or(0);
-    Ok(Json(RollAttackResponse {
-        total,
-        damage_rolls,
-    }))
+    (damage_rolls, total)
+}
+
+pub(crate) fn record_damage(
+    roll_journal: &(dyn RollJournal + Send + Sync),
+    endpoint: &str,
+    monster: &Monster,
+    attack_roll: &AttackRoll,
+    damage_rolls: &[Damage],
+    seed: u64,
+) {
+    if damage_rolls.is_empty() {
+        return;
+    }
+    roll_journal.record(RollRecord {
+        kind: RollKind::Damage,
+        endpoint: endpoint.to_string(),
+        monster: Some(monster.name.clone()),
+        expression: attack_roll
+            .damage
+            .iter()
+            .map(|damage_roll| damage_roll.roll.clone())
+            .collect::<Vec<_>>()
+            .join("+"),
+        result: damage_rolls.iter().map(|damage| damage.total).sum(),
+        rolls: damage_rolls
+            .iter()
+            .flat_map(|damage| damage.rolls.clone())
+            .collect(),
+        seed,
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::criticals::critical_strategy::build_critical_strategies;
     use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
     use crate::dice::dice_roller::DiceRollerImpl;
     use crate::dice::die_roller::DieRollerImpl;
+    use crate::history::HistoryFilter;
+    use crate::history::roll_journal::RollJournalImpl;
     use crate::monsters::{
-        Actions, AttackRoll, AttackType, Challenge, DamageRoll, Size, Skills, Speed, Stats,
+        Actions, AttackType, Challenge, DamageModifiers, DamageRoll, Size, Skills, Speed, Stats,
     };
+    use axum::http::Uri;
 
     #[tokio::test]
     async fn test_monster_not_found() {
@@ -110,6 +255,7 @@ mod tests {
         let result = roll_attack(
             Path(("fake-monster".to_string(), 0)),
             Query(HashMap::new()),
+            uri(),
             State(dependencies.clone()),
         )
         .await;
@@ -123,6 +269,7 @@ mod tests {
         let result = roll_attack(
             Path(("test monster".to_string(), 1)),
             Query(HashMap::new()),
+            uri(),
             State(dependencies.clone()),
         )
         .await
@@ -139,6 +286,7 @@ mod tests {
         let result = roll_attack(
             Path(("test monster".to_string(), 0)),
             Query(HashMap::new()),
+            uri(),
             State(dependencies.clone()),
         )
         .await
@@ -152,11 +300,12 @@ mod tests {
     #[tokio::test]
     async fn test_monster_attack_critical() {
         let dependencies = get_dependencies();
-        let mut critical_map: HashMap<Critical, String> = HashMap::new();
-        critical_map.insert(Critical::Critical, "".to_string());
+        let mut critical_map: HashMap<RollAttackQueryKeys, String> = HashMap::new();
+        critical_map.insert(RollAttackQueryKeys::Critical, "".to_string());
         let result = roll_attack(
             Path(("test monster".to_string(), 0)),
             Query(critical_map),
+            uri(),
             State(dependencies.clone()),
         )
         .await
@@ -165,6 +314,149 @@ mod tests {
 
         assert!(result.total > 0);
         assert_eq!(result.damage_rolls.first().unwrap().rolls.len(), 7);
+        assert_eq!(result.critical_rule, Some(CriticalRule::DoubleDice));
+    }
+
+    #[tokio::test]
+    async fn test_monster_attack_critical_rule_and_extra_dice() {
+        let dependencies = get_dependencies();
+        let result = roll_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([
+                (RollAttackQueryKeys::Critical, "".to_string()),
+                (RollAttackQueryKeys::CriticalRule, "maxPlusRoll".to_string()),
+                (RollAttackQueryKeys::ExtraDice, "1".to_string()),
+            ])),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let damage = result.damage_rolls.first().unwrap();
+        assert_eq!(result.critical_rule, Some(CriticalRule::MaxPlusRoll));
+        assert_eq!(damage.rolls.len(), 8);
+        assert!(matches!(result.total, 30..=50));
+    }
+
+    #[tokio::test]
+    async fn test_monster_attack_default_rule_only_on_critical() {
+        let dependencies = RollAttackDependencies {
+            critical_rule: CriticalRule::DoubleTotal,
+            ..get_dependencies()
+        };
+        let normal = roll_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+        let critical = roll_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::from([(
+                RollAttackQueryKeys::Critical,
+                "".to_string(),
+            )])),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(normal.critical_rule, None);
+        assert_eq!(critical.critical_rule, Some(CriticalRule::DoubleTotal));
+        assert_eq!(critical.total % 2, 0);
+    }
+
+    #[tokio::test]
+    async fn test_monster_attack_invalid_critical_options() {
+        for (key, value) in [
+            (RollAttackQueryKeys::CriticalRule, "triple"),
+            (RollAttackQueryKeys::ExtraDice, "-1"),
+        ] {
+            let result = roll_attack(
+                Path(("test monster".to_string(), 0)),
+                Query(HashMap::from([(key, value.to_string())])),
+                uri(),
+                State(get_dependencies()),
+            )
+            .await;
+
+            assert_eq!(result.err().unwrap().0, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_monster_attack_with_seed() {
+        let dependencies = get_dependencies();
+        let params = || {
+            Query(HashMap::from([(
+                RollAttackQueryKeys::Seed,
+                "8675309".to_string(),
+            )]))
+        };
+
+        let first = roll_attack(
+            Path(("test monster".to_string(), 0)),
+            params(),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+        let second = roll_attack(
+            Path(("test monster".to_string(), 0)),
+            params(),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(first.seed, 8675309);
+        assert_eq!(first.total, second.total);
+        assert_eq!(
+            first.damage_rolls.first().unwrap().rolls,
+            second.damage_rolls.first().unwrap().rolls
+        );
+    }
+
+    #[tokio::test]
+    async fn test_monster_attack_records_history() {
+        let dependencies = get_dependencies();
+
+        let result = roll_attack(
+            Path(("test monster".to_string(), 0)),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+        let missing = roll_attack(
+            Path(("test monster".to_string(), 1)),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let history = dependencies.roll_journal.entries(&HistoryFilter::default());
+        assert!(missing.damage_rolls.is_empty());
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].record.kind, RollKind::Damage);
+        assert_eq!(history[0].record.expression, "3d6+8");
+        assert_eq!(history[0].record.result, result.total);
     }
 
     fn get_test_monster() -> Monster {
@@ -250,6 +542,7 @@ mod tests {
             },
             image_url: "https://example.com/monster.jpg".to_string(),
             reactions: vec![],
+            damage_modifiers: DamageModifiers::default(),
         }
     }
 
@@ -263,6 +556,15 @@ mod tests {
             dice_expression_parser: Arc::new(dice_expression_parser),
             dice_roller: Arc::new(dice_roller),
             monster_map: Arc::new(monster_map),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            critical_strategies: Arc::new(build_critical_strategies()),
+            critical_rule: CriticalRule::DoubleDice,
         }
     }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static(
+            "/v1/monsters/test%20monster/roll/damage/0",
+        ))
+    }
 }
@@ -1,15 +1,25 @@
 use crate::dice::DiceExpressionParser;
 use crate::dice::{DiceRoller, Roll};
+use crate::handlers::resolve_seed;
+use crate::history::{RollJournal, RollKind, RollRecord};
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{OriginalUri, Path, Query, State};
 use axum::http::StatusCode;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct RollDiceHandlerDependencies {
     pub(crate) dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
     pub(crate) dice_roller: Arc<dyn DiceRoller + Send + Sync>,
+    pub(crate) roll_journal: Arc<dyn RollJournal + Send + Sync>,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum RollDiceQueryKeys {
+    #[serde(rename = "seed")]
+    Seed,
 }
 
 #[derive(Debug, Serialize, PartialEq)]
@@ -17,60 +27,99 @@ pub struct RollDiceResponse {
     expression: String,
     total: i32,
     rolls: Vec<Roll>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    successes: Option<i32>,
+    seed: u64,
 }
 
 pub async fn roll_dice(
     Path(expression): Path<String>,
+    Query(params): Query<HashMap<RollDiceQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
     State(dependencies): State<RollDiceHandlerDependencies>,
 ) -> Result<Json<RollDiceResponse>, (StatusCode, String)> {
-    let parsed_dice_expression_result = match dependencies.dice_expression_parser.parse(&expression)
+    let seed = resolve_seed(params.get(&RollDiceQueryKeys::Seed))?;
+    let parsed_dice_expression = match dependencies
+        .dice_expression_parser
+        .parse_expression(&expression)
     {
         Ok(result) => result,
         Err(error) => {
             return Err((StatusCode::BAD_REQUEST, error.to_string()));
         }
     };
-    let (rolls, total) = dependencies
+    let result = dependencies
         .dice_roller
-        .roll(&parsed_dice_expression_result);
+        .seeded(seed)
+        .roll_expression(&parsed_dice_expression);
+    dependencies.roll_journal.record(RollRecord {
+        kind: RollKind::Dice,
+        endpoint: uri.path().to_string(),
+        monster: None,
+        expression: expression.clone(),
+        result: result.total,
+        rolls: result.rolls.clone(),
+        seed,
+    });
     Ok(Json(RollDiceResponse {
         expression,
-        total,
-        rolls,
+        total: result.total,
+        rolls: result.rolls,
+        successes: result.successes,
+        seed,
     }))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dice::DiceExpressionParser;
-    use crate::dice::Die;
     use crate::dice::Die::{D6, Raw};
-    use crate::dice::DieRoller;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
     use crate::dice::dice_roller::DiceRollerImpl;
-    use std::sync::Arc;
+    use crate::dice::die_roller::DieRollerImpl;
+    use crate::dice::{DiceExpressionError, DiceTerm, Expression, Operator};
+    use crate::history::HistoryFilter;
+    use crate::history::roll_journal::RollJournalImpl;
+    use axum::http::Uri;
+
+    fn get_dependencies(
+        dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
+    ) -> RollDiceHandlerDependencies {
+        RollDiceHandlerDependencies {
+            dice_roller: Arc::new(DiceRollerImpl::new(Arc::new(DieRollerImpl::default()))),
+            dice_expression_parser,
+            roll_journal: Arc::new(RollJournalImpl::default()),
+        }
+    }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static("/v1/dice/roll/test"))
+    }
+
+    fn seed_query(seed: &str) -> Query<HashMap<RollDiceQueryKeys, String>> {
+        Query(HashMap::from([(RollDiceQueryKeys::Seed, seed.to_string())]))
+    }
 
     #[tokio::test]
     async fn test_roll_dice_error() {
         struct MockDiceExpressionParser {}
         impl DiceExpressionParser for MockDiceExpressionParser {
-            fn parse(&self, _: &str) -> Result<Vec<(Die, i32)>, String> {
-                Err(String::from("Invalid dice expression"))
-            }
-        }
-        struct MockDieRoller {}
-        impl DieRoller for MockDieRoller {
-            fn roll(&self, _: &Die) -> i32 {
-                panic!("Dice roller should not have been executed")
+            fn parse_expression(&self, _: &str) -> Result<Expression, DiceExpressionError> {
+                Err(DiceExpressionError {
+                    expression: "fake-expression".to_string(),
+                    position: 0,
+                    message: "Invalid dice expression".to_string(),
+                })
             }
         }
 
-        let state = RollDiceHandlerDependencies {
-            dice_roller: Arc::new(DiceRollerImpl::new(Arc::new(MockDieRoller {}))),
-            dice_expression_parser: Arc::new(MockDiceExpressionParser {}),
-        };
-
-        let result = roll_dice(Path("fake-expression".to_string()), State(state)).await;
+        let result = roll_dice(
+            Path("fake-expression".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies(Arc::new(MockDiceExpressionParser {}))),
+        )
+        .await;
 
         assert!(result.is_err());
     }
@@ -79,37 +128,165 @@ mod tests {
     async fn test_roll_dice_success() {
         struct MockDiceExpressionParser {}
         impl DiceExpressionParser for MockDiceExpressionParser {
-            fn parse(&self, _: &str) -> Result<Vec<(Die, i32)>, String> {
-                Ok(vec![(D6, 2), (Raw, 5)])
-            }
-        }
-        struct MockDieRoller {}
-        impl DieRoller for MockDieRoller {
-            fn roll(&self, _: &Die) -> i32 {
-                3
+            fn parse_expression(&self, _: &str) -> Result<Expression, DiceExpressionError> {
+                Ok(Expression::Binary(
+                    Operator::Add,
+                    Box::new(Expression::Dice(DiceTerm::new(2, D6))),
+                    Box::new(Expression::Constant(5)),
+                ))
             }
         }
 
-        let state = RollDiceHandlerDependencies {
-            dice_roller: Arc::new(DiceRollerImpl::new(Arc::new(MockDieRoller {}))),
-            dice_expression_parser: Arc::new(MockDiceExpressionParser {}),
-        };
-
-        let result = roll_dice(Path("fake-expression".to_string()), State(state))
-            .await
-            .unwrap();
+        let result = roll_dice(
+            Path("fake-expression".to_string()),
+            seed_query("7"),
+            uri(),
+            State(get_dependencies(Arc::new(MockDiceExpressionParser {}))),
+        )
+        .await
+        .unwrap()
+        .0;
 
         assert_eq!(
-            result.0,
+            result,
             RollDiceResponse {
                 expression: "fake-expression".to_string(),
-                total: 11,
-                rolls: vec![
-                    Roll { die: D6, value: 3 },
-                    Roll { die: D6, value: 3 },
-                    Roll { die: Raw, value: 5 }
-                ],
+                total: 7,
+                rolls: vec![Roll::new(D6, 1), Roll::new(D6, 1), Roll::new(Raw, 5)],
+                successes: None,
+                seed: 7,
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_roll_dice_replays_seed() {
+        let dependencies = get_dependencies(Arc::new(DiceExpressionParserImpl::default()));
+
+        let first = roll_dice(
+            Path("4d6dl1+1d20!".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+        let replay = roll_dice(
+            Path("4d6dl1+1d20!".to_string()),
+            seed_query(&first.seed.to_string()),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(first, replay);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_records_history() {
+        let dependencies = get_dependencies(Arc::new(DiceExpressionParserImpl::default()));
+
+        let result = roll_dice(
+            Path("2d6+3".to_string()),
+            seed_query("11"),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let history = dependencies.roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].record.kind, RollKind::Dice);
+        assert_eq!(history[0].record.endpoint, "/v1/dice/roll/test");
+        assert_eq!(history[0].record.expression, "2d6+3");
+        assert_eq!(history[0].record.result, result.total);
+        assert_eq!(history[0].record.seed, 11);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_invalid_seed() {
+        let result = roll_dice(
+            Path("1d20".to_string()),
+            seed_query("lucky"),
+            uri(),
+            State(get_dependencies(Arc::new(
+                DiceExpressionParserImpl::default(),
+            ))),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_success_pool() {
+        let result = roll_dice(
+            Path("4d6>=2".to_string()),
+            seed_query("7"),
+            uri(),
+            State(get_dependencies(Arc::new(
+                DiceExpressionParserImpl::default(),
+            ))),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(
+            result
+                .rolls
+                .iter()
+                .map(|roll| roll.value)
+                .collect::<Vec<_>>(),
+            vec![1, 1, 2, 2]
+        );
+        assert_eq!(result.successes, Some(2));
+        assert_eq!(result.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_long_chain() {
+        let dependencies = get_dependencies(Arc::new(DiceExpressionParserImpl::default()));
+
+        let result = roll_dice(
+            Path(vec!["1"; 500].join("+")),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+        let error = roll_dice(
+            Path(vec!["1"; 15_000].join("+")),
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(result.total, 500);
+        assert_eq!(error.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_total_overflow() {
+        let result = roll_dice(
+            Path("10000d1000000".to_string()),
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies(Arc::new(
+                DiceExpressionParserImpl::default(),
+            ))),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,323 @@
+use crate::dice::Roll;
+use crate::handlers::resolve_seed;
+use crate::handlers::roll_dice::{RollDiceHandlerDependencies, RollDiceQueryKeys};
+use crate::history::{RollKind, RollRecord};
+use axum::Json;
+use axum::extract::{OriginalUri, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const MAX_BATCH_ITEMS: usize = 100;
+const MAX_REPEAT: u32 = 100;
+const MAX_BATCH_ROLLS: u64 = 100_000;
+
+#[derive(Deserialize)]
+pub struct BatchRollRequest {
+    expression: String,
+    label: Option<String>,
+    repeat: Option<u32>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BatchRoll {
+    total: i32,
+    rolls: Vec<Roll>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    successes: Option<i32>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BatchRollResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    expression: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    results: Vec<BatchRoll>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BatchRollResponse {
+    results: Vec<BatchRollResult>,
+    seed: u64,
+}
+
+pub async fn roll_dice_batch(
+    Query(params): Query<HashMap<RollDiceQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
+    State(dependencies): State<RollDiceHandlerDependencies>,
+    Json(requests): Json<Vec<BatchRollRequest>>,
+) -> Result<Json<BatchRollResponse>, (StatusCode, String)> {
+    if requests.len() > MAX_BATCH_ITEMS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("A batch may contain at most {MAX_BATCH_ITEMS} expressions"),
+        ));
+    }
+    let seed = resolve_seed(params.get(&RollDiceQueryKeys::Seed))?;
+    let endpoint = uri.path().to_string();
+    let results =
+        tokio::task::spawn_blocking(move || roll_batch(&dependencies, &endpoint, seed, requests))
+            .await
+            .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+    Ok(Json(BatchRollResponse { results, seed }))
+}
+
+fn roll_batch(
+    dependencies: &RollDiceHandlerDependencies,
+    endpoint: &str,
+    seed: u64,
+    requests: Vec<BatchRollRequest>,
+) -> Vec<BatchRollResult> {
+    let dice_roller = dependencies.dice_roller.seeded(seed);
+    let mut remaining_rolls = MAX_BATCH_ROLLS;
+    requests
+        .into_iter()
+        .map(|request| {
+            let repeat = request.repeat.unwrap_or(1);
+            let rolled = match repeat {
+                1..=MAX_REPEAT => dependencies
+                    .dice_expression_parser
+                    .parse_expression(&request.expression)
+                    .map_err(|error| error.to_string()),
+                _ => Err(format!("Repeat must be between 1 and {MAX_REPEAT}")),
+            }
+            .and_then(|parsed_expression| {
+                let rolls = parsed_expression.max_rolls().saturating_mul(repeat as u64);
+                match rolls > remaining_rolls {
+                    true => Err(format!(
+                        "A batch may roll at most {MAX_BATCH_ROLLS} dice in total"
+                    )),
+                    false => {
+                        remaining_rolls -= rolls;
+                        Ok(parsed_expression)
+                    }
+                }
+            })
+            .map(|parsed_expression| {
+                let results = (0..repeat)
+                    .map(|_| dice_roller.roll_expression(&parsed_expression))
+                    .map(|result| BatchRoll {
+                        total: result.total,
+                        rolls: result.rolls,
+                        successes: result.successes,
+                    })
+                    .collect::<Vec<_>>();
+                dependencies.roll_journal.record(RollRecord {
+                    kind: RollKind::Dice,
+                    endpoint: endpoint.to_string(),
+                    monster: None,
+                    expression: request.expression.clone(),
+                    result: results
+                        .iter()
+                        .fold(0, |sum: i32, roll| sum.saturating_add(roll.total)),
+                    rolls: results
+                        .iter()
+                        .flat_map(|roll| roll.rolls.iter().cloned())
+                        .collect(),
+                    seed,
+                });
+                results
+            });
+            match rolled {
+                Ok(results) => BatchRollResult {
+                    label: request.label,
+                    expression: request.expression,
+                    results,
+                    error: None,
+                },
+                Err(error) => BatchRollResult {
+                    label: request.label,
+                    expression: request.expression,
+                    results: vec![],
+                    error: Some(error),
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+    use crate::dice::dice_roller::DiceRollerImpl;
+    use crate::dice::die_roller::DieRollerImpl;
+    use crate::history::HistoryFilter;
+    use crate::history::roll_journal::RollJournalImpl;
+    use axum::http::Uri;
+    use std::sync::Arc;
+
+    fn get_dependencies() -> RollDiceHandlerDependencies {
+        RollDiceHandlerDependencies {
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            dice_roller: Arc::new(DiceRollerImpl::new(Arc::new(DieRollerImpl::default()))),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+        }
+    }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static("/v1/dice/roll"))
+    }
+
+    fn request(expression: &str, label: Option<&str>, repeat: Option<u32>) -> BatchRollRequest {
+        BatchRollRequest {
+            expression: expression.to_string(),
+            label: label.map(|label| label.to_string()),
+            repeat,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_batch() {
+        let result = roll_dice_batch(
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies()),
+            Json(vec![
+                request("1d20+7", Some("Longsword"), None),
+                request("2d6", None, Some(3)),
+            ]),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.results.len(), 2);
+        let longsword = &result.results[0];
+        assert_eq!(longsword.label, Some("Longsword".to_string()));
+        assert_eq!(longsword.results.len(), 1);
+        assert!(matches!(longsword.results[0].total, 8..=27));
+        assert_eq!(result.results[1].results.len(), 3);
+        assert!(result.results.iter().all(|item| item.error.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_batch_records_history() {
+        let dependencies = get_dependencies();
+
+        let result = roll_dice_batch(
+            Query(HashMap::new()),
+            uri(),
+            State(dependencies.clone()),
+            Json(vec![
+                request("1d20", None, Some(2)),
+                request("banana", None, None),
+            ]),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert!(result.results[1].error.is_some());
+        let history = dependencies.roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].record.rolls.len(), 2);
+        assert_eq!(
+            history[0].record.result,
+            result.results[0]
+                .results
+                .iter()
+                .map(|roll| roll.total)
+                .sum::<i32>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_batch_roll_budget() {
+        let result = roll_dice_batch(
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies()),
+            Json(vec![
+                request("10000d6!", None, None),
+                request("1000d6", None, Some(MAX_REPEAT)),
+                request("1d6", None, None),
+                request("4", None, Some(MAX_REPEAT)),
+            ]),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(
+            result
+                .results
+                .iter()
+                .map(|item| item.error.is_some())
+                .collect::<Vec<_>>(),
+            vec![true, false, true, false]
+        );
+        assert_eq!(result.results[1].results.len(), MAX_REPEAT as usize);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_batch_item_errors() {
+        let result = roll_dice_batch(
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies()),
+            Json(vec![
+                request("1d20+banana", None, None),
+                request("1d6", None, Some(0)),
+                request("1d4", None, None),
+            ]),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert!(result.results[0].error.is_some());
+        assert!(result.results[0].results.is_empty());
+        assert!(result.results[1].error.is_some());
+        assert!(result.results[2].error.is_none());
+        assert_eq!(result.results[2].results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_batch_replays_seed() {
+        let batch = || {
+            Json(vec![
+                request("4d6dl1", None, Some(6)),
+                request("1d20", None, None),
+            ])
+        };
+        let seed = || {
+            Query(HashMap::from([(
+                RollDiceQueryKeys::Seed,
+                "2024".to_string(),
+            )]))
+        };
+
+        let first = roll_dice_batch(seed(), uri(), State(get_dependencies()), batch())
+            .await
+            .unwrap()
+            .0;
+        let second = roll_dice_batch(seed(), uri(), State(get_dependencies()), batch())
+            .await
+            .unwrap()
+            .0;
+
+        assert_eq!(first.seed, 2024);
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_roll_dice_batch_too_large() {
+        let result = roll_dice_batch(
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies()),
+            Json(
+                (0..=MAX_BATCH_ITEMS)
+                    .map(|_| request("1d6", None, None))
+                    .collect(),
+            ),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+}
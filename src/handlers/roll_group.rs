@@ -0,0 +1,279 @@
+use crate::dice::Roll;
+use crate::handlers::MonsterRollerDependencies;
+use crate::handlers::roll_stat::{
+    RollStatQueryKeys, find_monster, resolve_options, roll_monster_stat,
+};
+use crate::stats::{AdvantageSources, AdvantageType, DifficultyCheck, KeptRoll};
+use axum::Json;
+use axum::extract::{OriginalUri, Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const MAX_GROUP_SIZE: u32 = 100;
+
+#[derive(Deserialize)]
+pub struct GroupMember {
+    monster: String,
+    count: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupRollResult {
+    monster: String,
+    #[serde(rename = "firstRoll")]
+    first_roll: Vec<Roll>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "secondRoll")]
+    second_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "thirdRoll")]
+    third_roll: Option<Vec<Roll>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "bonusRoll")]
+    bonus_roll: Option<Vec<Roll>>,
+    result: i32,
+    kept: KeptRoll,
+    natural: i32,
+    #[serde(rename = "isCriticalSuccess")]
+    is_critical_success: bool,
+    #[serde(rename = "isCriticalFailure")]
+    is_critical_failure: bool,
+    passed: bool,
+    margin: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupRollResponse {
+    dc: i32,
+    results: Vec<GroupRollResult>,
+    succeeded: Vec<String>,
+    failed: Vec<String>,
+    #[serde(rename = "advantageSources")]
+    advantage_sources: AdvantageSources,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "rolledWith")]
+    rolled_with: Option<AdvantageType>,
+    seed: u64,
+}
+
+pub async fn roll_group<T>(
+    Path(stat): Path<T>,
+    Query(params): Query<HashMap<RollStatQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
+    State(dependencies): State<MonsterRollerDependencies<T>>,
+    Json(members): Json<Vec<GroupMember>>,
+) -> Result<Json<GroupRollResponse>, (StatusCode, String)> {
+    let options = resolve_options(&params, dependencies.dice_expression_parser.as_ref())?;
+    let Some(dc) = options.difficulty_class else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A `dc` integer is required".to_string(),
+        ));
+    };
+    let size = members
+        .iter()
+        .map(|member| member.count.unwrap_or(1))
+        .try_fold(0u32, |size, count| match count {
+            0 => None,
+            count => size.checked_add(count),
+        });
+    if !matches!(size, Some(1..=MAX_GROUP_SIZE)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("A group must contain between 1 and {MAX_GROUP_SIZE} monsters"),
+        ));
+    }
+    let monsters = members
+        .iter()
+        .map(|member| {
+            find_monster(&dependencies, &member.monster)
+                .map(|monster| (monster, member.count.unwrap_or(1)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let stats_roller = dependencies.stats_roller.seeded(options.seed);
+    let mut results = vec![];
+    for (monster, count) in monsters {
+        for copy in 1..=count {
+            let rolls = roll_monster_stat(
+                &dependencies,
+                stats_roller.as_ref(),
+                monster,
+                &stat,
+                &options,
+                uri.path(),
+            )?;
+            let check = DifficultyCheck::against(rolls.result, dc);
+            results.push(GroupRollResult {
+                monster: match count {
+                    1 => monster.name.clone(),
+                    _ => format!("{} {copy}", monster.name),
+                },
+                first_roll: rolls.first_roll,
+                second_roll: rolls.second_roll,
+                third_roll: rolls.third_roll,
+                bonus_roll: rolls.bonus_roll,
+                result: rolls.result,
+                kept: rolls.kept,
+                natural: rolls.natural,
+                is_critical_success: rolls.is_critical_success,
+                is_critical_failure: rolls.is_critical_failure,
+                passed: check.passed,
+                margin: check.margin,
+            });
+        }
+    }
+    let (succeeded, failed) = results
+        .iter()
+        .partition::<Vec<_>, _>(|result| result.passed);
+    let names = |results: Vec<&GroupRollResult>| {
+        results
+            .into_iter()
+            .map(|result| result.monster.clone())
+            .collect::<Vec<_>>()
+    };
+    Ok(Json(GroupRollResponse {
+        dc,
+        succeeded: names(succeeded),
+        failed: names(failed),
+        results,
+        advantage_sources: options.advantage_sources,
+        rolled_with: options.advantage,
+        seed: options.seed,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
+    use crate::dice::dice_roller::DiceRollerImpl;
+    use crate::dice::sequence_die_roller::SequenceDieRoller;
+    use crate::history::roll_journal::RollJournalImpl;
+    use crate::history::{HistoryFilter, RollKind};
+    use crate::monsters::Monster;
+    use crate::stats::StatType;
+    use crate::stats::modifier_extractor::ModifierExtractor;
+    use crate::stats::stat_roller::StatRollerImpl;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::index::vec_to_map;
+    use crate::utilities::load_from_json::load_from_json;
+    use axum::http::Uri;
+    use std::sync::Arc;
+
+    fn get_dependencies(values: Vec<i32>) -> MonsterRollerDependencies<StatType> {
+        let monsters = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH);
+        MonsterRollerDependencies {
+            monster_map: Arc::new(vec_to_map(&monsters, |monster| monster.name.to_lowercase())),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(SequenceDieRoller::new(values)),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: Arc::new(ModifierExtractor::new(|_, _| Some(2))),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        }
+    }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static("/v1/group/roll/throw/dex"))
+    }
+
+    fn dc(dc: &str) -> Query<HashMap<RollStatQueryKeys, String>> {
+        Query(HashMap::from([(
+            RollStatQueryKeys::DifficultyClass,
+            dc.to_string(),
+        )]))
+    }
+
+    fn member(monster: &str, count: Option<u32>) -> GroupMember {
+        GroupMember {
+            monster: monster.to_string(),
+            count,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roll_group() {
+        let dependencies = get_dependencies(vec![12, 13, 20, 1]);
+
+        let result = roll_group(
+            Path(StatType::Dexterity),
+            dc("15"),
+            uri(),
+            State(dependencies.clone()),
+            Json(vec![member("goblin", Some(3)), member("Orc", None)]),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.dc, 15);
+        assert_eq!(
+            result
+                .results
+                .iter()
+                .map(|result| (result.result, result.margin))
+                .collect::<Vec<_>>(),
+            vec![(14, -1), (15, 0), (22, 7), (3, -12)]
+        );
+        assert_eq!(result.succeeded, vec!["Goblin 2", "Goblin 3"]);
+        assert_eq!(result.failed, vec!["Goblin 1", "Orc"]);
+        assert!(result.results[3].is_critical_failure);
+        assert_eq!(
+            dependencies
+                .roll_journal
+                .entries(&HistoryFilter::default())
+                .len(),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_roll_group_requires_dc() {
+        let result = roll_group(
+            Path(StatType::Dexterity),
+            Query(HashMap::new()),
+            uri(),
+            State(get_dependencies(vec![10])),
+            Json(vec![member("goblin", None)]),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_group_invalid_size() {
+        for members in [
+            vec![],
+            vec![member("goblin", Some(0))],
+            vec![member("goblin", Some(MAX_GROUP_SIZE + 1))],
+        ] {
+            let result = roll_group(
+                Path(StatType::Dexterity),
+                dc("10"),
+                uri(),
+                State(get_dependencies(vec![])),
+                Json(members),
+            )
+            .await;
+
+            assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roll_group_unknown_monster() {
+        let result = roll_group(
+            Path(StatType::Dexterity),
+            dc("10"),
+            uri(),
+            State(get_dependencies(vec![10])),
+            Json(vec![member("Not A Monster", None)]),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::history::{HistoryEntry, HistoryFilter, HistoryStats, RollJournal, RollKind};
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct RollHistoryDependencies {
+    pub(crate) roll_journal: Arc<dyn RollJournal + Send + Sync>,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum RollHistoryQueryKeys {
+    #[serde(rename = "monster")]
+    Monster,
+    #[serde(rename = "kind")]
+    Kind,
+    #[serde(rename = "since")]
+    Since,
+    #[serde(rename = "until")]
+    Until,
+    #[serde(rename = "limit")]
+    Limit,
+}
+
+pub async fn list_history(
+    Query(params): Query<HashMap<RollHistoryQueryKeys, String>>,
+    State(dependencies): State<RollHistoryDependencies>,
+) -> Result<Json<Vec<HistoryEntry>>, (StatusCode, String)> {
+    let filter = build_filter(&params)?;
+    let limit = parse_number::<usize>(&params, RollHistoryQueryKeys::Limit, "Limit")?;
+    let entries = dependencies.roll_journal.entries(&filter);
+    let skipped = limit.map_or(0, |limit| entries.len().saturating_sub(limit));
+    Ok(Json(entries.into_iter().skip(skipped).collect()))
+}
+
+pub async fn history_stats(
+    Query(params): Query<HashMap<RollHistoryQueryKeys, String>>,
+    State(dependencies): State<RollHistoryDependencies>,
+) -> Result<Json<HistoryStats>, (StatusCode, String)> {
+    let filter = build_filter(&params)?;
+    Ok(Json(HistoryStats::from_entries(
+        &dependencies.roll_journal.entries(&filter),
+    )))
+}
+
+fn build_filter(
+    params: &HashMap<RollHistoryQueryKeys, String>,
+) -> Result<HistoryFilter, (StatusCode, String)> {
+    Ok(HistoryFilter {
+        monster: params.get(&RollHistoryQueryKeys::Monster).cloned(),
+        kind: params
+            .get(&RollHistoryQueryKeys::Kind)
+            .map(|kind| kind.parse::<RollKind>())
+            .transpose()
+            .map_err(|error| (StatusCode::BAD_REQUEST, error))?,
+        since: parse_number::<u64>(params, RollHistoryQueryKeys::Since, "Since")?,
+        until: parse_number::<u64>(params, RollHistoryQueryKeys::Until, "Until")?,
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(
+    params: &HashMap<RollHistoryQueryKeys, String>,
+    key: RollHistoryQueryKeys,
+    name: &str,
+) -> Result<Option<T>, (StatusCode, String)> {
+    params
+        .get(&key)
+        .map(|value| value.parse::<T>())
+        .transpose()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("{name} must be a non-negative integer"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::{Die, Roll};
+    use crate::history::RollRecord;
+    use crate::history::roll_journal::RollJournalImpl;
+
+    fn get_dependencies() -> RollHistoryDependencies {
+        let roll_journal = RollJournalImpl::default();
+        for (kind, monster, value) in [
+            (RollKind::Dice, None, 20),
+            (RollKind::Save, Some("Goblin"), 1),
+            (RollKind::Skill, Some("Goblin"), 20),
+        ] {
+            roll_journal.record(RollRecord {
+                kind,
+                endpoint: "/v1/test".to_string(),
+                monster: monster.map(|monster: &str| monster.to_string()),
+                expression: "1d20".to_string(),
+                result: value,
+                rolls: vec![Roll::new(Die::D20, value)],
+                seed: 1,
+            });
+        }
+        RollHistoryDependencies {
+            roll_journal: Arc::new(roll_journal),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_history_filters() {
+        let result = list_history(
+            Query(HashMap::from([
+                (RollHistoryQueryKeys::Monster, "goblin".to_string()),
+                (RollHistoryQueryKeys::Limit, "1".to_string()),
+            ])),
+            State(get_dependencies()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].record.kind, RollKind::Skill);
+    }
+
+    #[tokio::test]
+    async fn test_list_history_invalid_kind() {
+        let result = list_history(
+            Query(HashMap::from([(
+                RollHistoryQueryKeys::Kind,
+                "fumble".to_string(),
+            )])),
+            State(get_dependencies()),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_history_stats() {
+        let result = history_stats(Query(HashMap::new()), State(get_dependencies()))
+            .await
+            .unwrap()
+            .0;
+
+        assert_eq!(result.rolls, 3);
+        assert_eq!(result.natural_twenties, 1);
+        assert_eq!(result.natural_ones, 1);
+        assert_eq!(result.by_monster.get("Goblin"), Some(&2));
+    }
+}
@@ -1,58 +1,220 @@
-use crate::handlers::{MonsterRollerDependencies, StatRollResponse};
-use crate::stats::AdvantageType;
+use crate::dice::{DiceExpressionParser, Expression, Roll};
+use crate::handlers::{
+    MonsterRollerDependencies, StatRollResponse, resolve_advantage_sources, resolve_bonus,
+    resolve_critical_threshold, resolve_difficulty_class, resolve_flag, resolve_seed,
+};
+use crate::history::RollRecord;
+use crate::monsters::Monster;
+use crate::stats::{
+    AdvantageSources, AdvantageType, DifficultyCheck, KeptRoll, StatRoll, StatRoller,
+};
 use axum::Json;
-use axum::extract::{Path, Query, State};
+use axum::extract::{OriginalUri, Path, Query, State};
 use axum::http::StatusCode;
+use serde::Deserialize;
 use std::collections::HashMap;
 
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum RollStatQueryKeys {
+    #[serde(rename = "advantage")]
+    Advantage,
+    #[serde(rename = "disadvantage")]
+    Disadvantage,
+    #[serde(rename = "elvenAccuracy")]
+    ElvenAccuracy,
+    #[serde(rename = "seed")]
+    Seed,
+    #[serde(rename = "critRange")]
+    CriticalRange,
+    #[serde(rename = "dc")]
+    DifficultyClass,
+    #[serde(rename = "bonus")]
+    Bonus,
+}
+
+pub(crate) struct RollStatOptions {
+    pub(crate) advantage_sources: AdvantageSources,
+    pub(crate) advantage: Option<AdvantageType>,
+    pub(crate) critical_threshold: i32,
+    pub(crate) difficulty_class: Option<i32>,
+    pub(crate) bonus: Option<(String, Expression)>,
+    pub(crate) seed: u64,
+}
+
 pub async fn roll_stat<T>(
     Path((monster_name, stat)): Path<(String, T)>,
-    Query(advantage): Query<HashMap<AdvantageType, String>>,
+    Query(params): Query<HashMap<RollStatQueryKeys, String>>,
+    OriginalUri(uri): OriginalUri,
     State(dependencies): State<MonsterRollerDependencies<T>>,
 ) -> Result<Json<StatRollResponse>, (StatusCode, String)> {
-    let selected_monster = match dependencies.monster_map.get(&monster_name.to_lowercase()) {
-        None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                format!("Monster `{monster_name}` not found"),
-            ));
-        }
-        Some(monster) => monster,
-    };
-    let modifier = match dependencies
-        .modifier_extractor
-        .extract(&stat, selected_monster)
-    {
+    let options = resolve_options(&params, dependencies.dice_expression_parser.as_ref())?;
+    let selected_monster = find_monster(&dependencies, &monster_name)?;
+    let rolls = roll_monster_stat(
+        &dependencies,
+        dependencies.stats_roller.seeded(options.seed).as_ref(),
+        selected_monster,
+        &stat,
+        &options,
+        uri.path(),
+    )?;
+    Ok(Json(StatRollResponse {
+        first_roll: rolls.first_roll,
+        second_roll: rolls.second_roll,
+        third_roll: rolls.third_roll,
+        bonus_roll: rolls.bonus_roll,
+        result: rolls.result,
+        kept: rolls.kept,
+        natural: rolls.natural,
+        is_critical_success: rolls.is_critical_success,
+        is_critical_failure: rolls.is_critical_failure,
+        check: options
+            .difficulty_class
+            .map(|dc| DifficultyCheck::against(rolls.result, dc)),
+        advantage_sources: options.advantage_sources,
+        rolled_with: options.advantage,
+        seed: options.seed,
+    }))
+}
+
+pub(crate) fn resolve_options(
+    params: &HashMap<RollStatQueryKeys, String>,
+    dice_expression_parser: &(dyn DiceExpressionParser + Send + Sync),
+) -> Result<RollStatOptions, (StatusCode, String)> {
+    let advantage_sources = resolve_advantage_sources(
+        params.get(&RollStatQueryKeys::Advantage),
+        params.get(&RollStatQueryKeys::Disadvantage),
+        resolve_flag(
+            params.get(&RollStatQueryKeys::ElvenAccuracy),
+            "elvenAccuracy",
+        )?,
+    );
+    Ok(RollStatOptions {
+        advantage: advantage_sources.resolve(),
+        advantage_sources,
+        critical_threshold: resolve_critical_threshold(
+            params.get(&RollStatQueryKeys::CriticalRange),
+        )?,
+        difficulty_class: resolve_difficulty_class(
+            params.get(&RollStatQueryKeys::DifficultyClass),
+        )?,
+        bonus: resolve_bonus(
+            dice_expression_parser,
+            params.get(&RollStatQueryKeys::Bonus),
+        )?,
+        seed: resolve_seed(params.get(&RollStatQueryKeys::Seed))?,
+    })
+}
+
+pub(crate) fn find_monster<'a, T>(
+    dependencies: &'a MonsterRollerDependencies<T>,
+    monster_name: &str,
+) -> Result<&'a Monster, (StatusCode, String)> {
+    dependencies
+        .monster_map
+        .get(&monster_name.to_lowercase())
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Monster `{monster_name}` not found"),
+        ))
+}
+
+pub(crate) fn roll_monster_stat<T>(
+    dependencies: &MonsterRollerDependencies<T>,
+    stats_roller: &(dyn StatRoller + Send + Sync),
+    monster: &Monster,
+    stat: &T,
+    options: &RollStatOptions,
+    endpoint: &str,
+) -> Result<StatRoll, (StatusCode, String)> {
+    let modifier = match dependencies.modifier_extractor.extract(stat, monster) {
         None => {
             return Err((StatusCode::NOT_FOUND, "No stat modifier found".to_string()));
         }
         Some(modifier) => modifier,
     };
-    let rolls = dependencies
-        .stats_roller
-        .roll_stat(modifier, &advantage.keys().last());
-    Ok(Json(StatRollResponse {
-        first_roll: rolls.first_roll,
-        second_roll: rolls.second_roll,
+    let rolls = stats_roller.roll_stat(
+        modifier,
+        &options.advantage.as_ref(),
+        options.critical_threshold,
+        options.bonus.as_ref().map(|(_, bonus)| bonus),
+    );
+    dependencies.roll_journal.record(RollRecord {
+        kind: dependencies.roll_kind,
+        endpoint: endpoint.to_string(),
+        monster: Some(monster.name.clone()),
+        expression: format!(
+            "{}{modifier:+}{}",
+            d20_expression(&options.advantage),
+            options
+                .bonus
+                .as_ref()
+                .map_or("", |(suffix, _)| suffix.as_str()),
+        ),
         result: rolls.result,
-    }))
+        rolls: recorded_rolls(&rolls),
+        seed: options.seed,
+    });
+    Ok(rolls)
+}
+
+pub(crate) fn d20_expression(advantage: &Option<AdvantageType>) -> &'static str {
+    match advantage {
+        None => "1d20",
+        Some(AdvantageType::Advantage) => "2d20kh1",
+        Some(AdvantageType::Disadvantage) => "2d20kl1",
+        Some(AdvantageType::ElvenAccuracy) => "3d20kh1",
+    }
+}
+
+pub(crate) fn recorded_rolls(stat_roll: &StatRoll) -> Vec<Roll> {
+    let Some(second_roll) = &stat_roll.second_roll else {
+        return [
+            stat_roll.first_roll.clone(),
+            stat_roll.bonus_roll.clone().unwrap_or_default(),
+        ]
+        .concat();
+    };
+    let mark = |rolls: &[Roll], kept: bool| {
+        rolls
+            .iter()
+            .map(|roll| Roll {
+                kept,
+                ..roll.clone()
+            })
+            .collect::<Vec<Roll>>()
+    };
+    [
+        mark(&stat_roll.first_roll, stat_roll.kept == KeptRoll::First),
+        mark(second_roll, stat_roll.kept == KeptRoll::Second),
+        stat_roll
+            .third_roll
+            .as_ref()
+            .map(|third_roll| mark(third_roll, stat_roll.kept == KeptRoll::Third))
+            .unwrap_or_default(),
+        stat_roll.bonus_roll.clone().unwrap_or_default(),
+    ]
+    .concat()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dice::Roll;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
     use crate::dice::dice_roller::DiceRollerImpl;
     use crate::dice::die_roller::DieRollerImpl;
     use crate::handlers::MonsterRollerDependencies;
     use crate::handlers::roll_stat::roll_stat;
-    use crate::monsters::{Challenge, Monster, Size, Skills, Speed, Stats};
+    use crate::history::roll_journal::RollJournalImpl;
+    use crate::history::{HistoryFilter, RollJournal, RollKind};
+    use crate::monsters::{Challenge, DamageModifiers, Monster, Size, Skills, Speed, Stats};
     use crate::stats::StatType;
     use crate::stats::modifier_extractor::{
         ModifierExtractor, build_saving_throw_modifier_extractor,
     };
     use crate::stats::stat_roller::StatRollerImpl;
     use axum::http::StatusCode;
+    use axum::http::Uri;
     use std::cmp::{max, min};
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -68,12 +230,16 @@ mod tests {
             stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
                 Arc::new(DieRollerImpl::default()),
             )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
             modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
         };
 
         let result = roll_stat(
             Path(("test_monster".to_string(), StatType::Wisdom)),
             Query(HashMap::new()),
+            uri(),
             State(dependencies.clone()),
         )
         .await;
@@ -90,12 +256,16 @@ mod tests {
             stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
                 Arc::new(DieRollerImpl::default()),
             )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
             modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
         };
 
         let result = roll_stat(
             Path(("test_monster".to_string(), StatType::Wisdom)),
             Query(HashMap::new()),
+            uri(),
             State(dependencies.clone()),
         )
         .await;
@@ -115,12 +285,16 @@ mod tests {
             stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
                 Arc::new(DieRollerImpl::default()),
             )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
             modifier_extractor: Arc::new(ModifierExtractor::new(|_, _| None)),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
         };
 
         let result = roll_stat(
             Path(("test_monster".to_string(), StatType::Wisdom)),
             Query(HashMap::new()),
+            uri(),
             State(dependencies.clone()),
         )
         .await;
@@ -140,16 +314,20 @@ mod tests {
             stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
                 Arc::new(DieRollerImpl::default()),
             )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
             modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
         };
 
         let result = roll_stat(
             Path(("test_monster".to_string(), StatType::Wisdom)),
             Query(
-                [(AdvantageType::Advantage, "".to_string())]
+                [(RollStatQueryKeys::Advantage, "".to_string())]
                     .into_iter()
                     .collect(),
             ),
+            uri(),
             State(dependencies.clone()),
         )
         .await;
@@ -177,16 +355,20 @@ mod tests {
             stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
                 Arc::new(DieRollerImpl::default()),
             )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
             modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
         };
 
         let result = roll_stat(
             Path(("test_monster".to_string(), StatType::Wisdom)),
             Query(
-                [(AdvantageType::Disadvantage, "".to_string())]
+                [(RollStatQueryKeys::Disadvantage, "".to_string())]
                     .into_iter()
                     .collect(),
             ),
+            uri(),
             State(dependencies.clone()),
         )
         .await;
@@ -203,6 +385,443 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_roll_saving_throw_sources_cancel() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([
+                (
+                    RollStatQueryKeys::Advantage,
+                    "flanking, reckless".to_string(),
+                ),
+                (RollStatQueryKeys::Disadvantage, "".to_string()),
+                (RollStatQueryKeys::ElvenAccuracy, "".to_string()),
+            ])),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert!(result.second_roll.is_none());
+        assert_eq!(result.rolled_with, None);
+        assert_eq!(
+            result.advantage_sources.advantage,
+            vec!["flanking", "reckless"]
+        );
+        assert_eq!(result.advantage_sources.disadvantage, vec!["disadvantage"]);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_with_elven_accuracy() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+        let roll_journal = Arc::new(RollJournalImpl::default());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: roll_journal.clone(),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([
+                (RollStatQueryKeys::Advantage, "hidden".to_string()),
+                (RollStatQueryKeys::ElvenAccuracy, "".to_string()),
+            ])),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.rolled_with, Some(AdvantageType::ElvenAccuracy));
+        assert_eq!(
+            result.result,
+            [
+                &result.first_roll,
+                result.second_roll.as_ref().unwrap(),
+                result.third_roll.as_ref().unwrap(),
+            ]
+            .into_iter()
+            .map(|rolls| total_roll(rolls))
+            .max()
+            .unwrap()
+        );
+        let history = roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history[0].record.expression, "3d20kh1+13");
+        assert_eq!(
+            history[0]
+                .record
+                .rolls
+                .iter()
+                .filter(|roll| roll.kept)
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_with_elven_accuracy_false() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([
+                (RollStatQueryKeys::Advantage, "hidden".to_string()),
+                (RollStatQueryKeys::ElvenAccuracy, "false".to_string()),
+            ])),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.rolled_with, Some(AdvantageType::Advantage));
+        assert!(!result.advantage_sources.elven_accuracy);
+        assert!(result.second_roll.is_some());
+        assert!(result.third_roll.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_invalid_elven_accuracy() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::ElvenAccuracy,
+                "yes".to_string(),
+            )])),
+            uri(),
+            State(dependencies),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_with_seed() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+        let params = || {
+            Query(HashMap::from([
+                (RollStatQueryKeys::Seed, "31337".to_string()),
+                (RollStatQueryKeys::Advantage, "".to_string()),
+            ]))
+        };
+
+        let first = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            params(),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+        let second = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            params(),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(first.seed, 31337);
+        assert_eq!(first.first_roll, second.first_roll);
+        assert_eq!(first.second_roll, second.second_roll);
+        assert_eq!(first.result, second.result);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_invalid_seed() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(RollStatQueryKeys::Seed, "-1".to_string())])),
+            uri(),
+            State(dependencies),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_records_history() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+        let roll_journal = Arc::new(RollJournalImpl::default());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: roll_journal.clone(),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::Disadvantage,
+                "".to_string(),
+            )])),
+            uri(),
+            State(dependencies),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let history = roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history.len(), 1);
+        let record = &history[0].record;
+        assert_eq!(record.kind, RollKind::Save);
+        assert_eq!(record.monster, Some("Test Monster".to_string()));
+        assert_eq!(record.expression, "2d20kl1+13");
+        assert_eq!(record.result, result.result);
+        assert_eq!(record.rolls.len(), 4);
+        assert_eq!(record.rolls.iter().filter(|roll| roll.kept).count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_reports_critical_range() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([
+                (RollStatQueryKeys::CriticalRange, "2".to_string()),
+                (RollStatQueryKeys::Advantage, "".to_string()),
+            ])),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let kept = match result.kept {
+            KeptRoll::First => &result.first_roll,
+            KeptRoll::Second => result.second_roll.as_ref().unwrap(),
+            KeptRoll::Third => result.third_roll.as_ref().unwrap(),
+        };
+        assert_eq!(result.natural, kept[0].value);
+        assert_eq!(result.result, total_roll(kept));
+        assert_eq!(result.is_critical_success, result.natural >= 2);
+        assert_eq!(result.is_critical_failure, result.natural == 1);
+
+        let invalid = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::CriticalRange,
+                "21".to_string(),
+            )])),
+            uri(),
+            State(dependencies),
+        )
+        .await;
+
+        assert_eq!(invalid.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_against_dc() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: Arc::new(RollJournalImpl::default()),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::DifficultyClass,
+                "25".to_string(),
+            )])),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let check = result.check.unwrap();
+        assert_eq!(check.dc, 25);
+        assert_eq!(check.margin, result.result - 25);
+        assert_eq!(check.passed, result.result >= 25);
+
+        let invalid = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::DifficultyClass,
+                "hard".to_string(),
+            )])),
+            uri(),
+            State(dependencies),
+        )
+        .await;
+
+        assert_eq!(invalid.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_roll_saving_throw_with_bonus_dice() {
+        let mut monster_map = HashMap::new();
+        monster_map.insert("test_monster".to_string(), get_test_monster());
+        let roll_journal = Arc::new(RollJournalImpl::default());
+
+        let dependencies = MonsterRollerDependencies {
+            monster_map: Arc::new(monster_map),
+            stats_roller: Arc::new(StatRollerImpl::new(Arc::new(DiceRollerImpl::new(
+                Arc::new(DieRollerImpl::default()),
+            )))),
+            dice_expression_parser: Arc::new(DiceExpressionParserImpl::default()),
+            modifier_extractor: get_modifier_extractor(),
+            roll_journal: roll_journal.clone(),
+            roll_kind: RollKind::Save,
+        };
+
+        let result = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::Bonus,
+                "1d4".to_string(),
+            )])),
+            uri(),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let bonus_roll = result.bonus_roll.unwrap();
+        assert_eq!(bonus_roll.len(), 1);
+        assert_eq!(
+            result.result,
+            total_roll(&result.first_roll) + bonus_roll[0].value
+        );
+        let history = roll_journal.entries(&HistoryFilter::default());
+        assert_eq!(history[0].record.expression, "1d20+13+1d4");
+        assert_eq!(history[0].record.rolls.len(), 3);
+
+        let invalid = roll_stat(
+            Path(("test_monster".to_string(), StatType::Wisdom)),
+            Query(HashMap::from([(
+                RollStatQueryKeys::Bonus,
+                "bless".to_string(),
+            )])),
+            uri(),
+            State(dependencies),
+        )
+        .await;
+
+        assert_eq!(invalid.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    fn uri() -> OriginalUri {
+        OriginalUri(Uri::from_static("/v1/monsters/test_monster/roll/throw/wis"))
+    }
+
     fn get_test_monster() -> Monster {
         Monster {
             name: "Test Monster".to_string(),
@@ -274,6 +893,7 @@ mod tests {
             },
             image_url: "https://example.com/monster.jpg".to_string(),
             reactions: vec![],
+            damage_modifiers: DamageModifiers::default(),
         }
     }
 
@@ -0,0 +1,124 @@
+use crate::suggestions::{MonsterSuggester, Suggestion};
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const DEFAULT_SUGGESTIONS: usize = 10;
+const MAX_SUGGESTIONS: usize = 50;
+
+#[derive(Clone)]
+pub struct SuggestMonstersDependencies {
+    pub(crate) monster_suggester: Arc<dyn MonsterSuggester + Send + Sync>,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum SuggestMonstersQueryKeys {
+    #[serde(rename = "prefix")]
+    Prefix,
+    #[serde(rename = "limit")]
+    Limit,
+}
+
+pub async fn suggest_monsters(
+    Query(params): Query<HashMap<SuggestMonstersQueryKeys, String>>,
+    State(dependencies): State<SuggestMonstersDependencies>,
+) -> Result<Json<Vec<Suggestion>>, (StatusCode, String)> {
+    let Some(prefix) = params.get(&SuggestMonstersQueryKeys::Prefix) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A `prefix` is required".to_string(),
+        ));
+    };
+    let limit = match params.get(&SuggestMonstersQueryKeys::Limit) {
+        None => DEFAULT_SUGGESTIONS,
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit @ 1..=MAX_SUGGESTIONS) => limit,
+            _ => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Limit must be between 1 and {MAX_SUGGESTIONS}"),
+                ));
+            }
+        },
+    };
+    Ok(Json(dependencies.monster_suggester.suggest(prefix, limit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monsters::Monster;
+    use crate::suggestions::monster_suggester::MonsterSuggesterImpl;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::load_from_json::load_from_json;
+
+    fn get_dependencies() -> SuggestMonstersDependencies {
+        SuggestMonstersDependencies {
+            monster_suggester: Arc::new(MonsterSuggesterImpl::new(
+                &load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suggest_monsters() {
+        let result = suggest_monsters(
+            Query(HashMap::from([
+                (SuggestMonstersQueryKeys::Prefix, "dra".to_string()),
+                (SuggestMonstersQueryKeys::Limit, "2".to_string()),
+            ])),
+            State(get_dependencies()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.len(), 2);
+        assert!(
+            result
+                .iter()
+                .all(|suggestion| suggestion.name.contains("Dra"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suggest_monsters_default_limit() {
+        let result = suggest_monsters(
+            Query(HashMap::from([(
+                SuggestMonstersQueryKeys::Prefix,
+                "a".to_string(),
+            )])),
+            State(get_dependencies()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.len(), DEFAULT_SUGGESTIONS);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_monsters_invalid_params() {
+        for params in [
+            HashMap::new(),
+            HashMap::from([
+                (SuggestMonstersQueryKeys::Prefix, "a".to_string()),
+                (SuggestMonstersQueryKeys::Limit, "0".to_string()),
+            ]),
+            HashMap::from([
+                (SuggestMonstersQueryKeys::Prefix, "a".to_string()),
+                (
+                    SuggestMonstersQueryKeys::Limit,
+                    (MAX_SUGGESTIONS + 1).to_string(),
+                ),
+            ]),
+        ] {
+            let result = suggest_monsters(Query(params), State(get_dependencies())).await;
+
+            assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::monsters::Monster;
+use crate::validation::{Anomaly, AnomalyKind, MonsterValidator};
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct ValidateMonstersDependencies {
+    pub(crate) monsters: Arc<Vec<Monster>>,
+    pub(crate) monster_validator: Arc<dyn MonsterValidator + Send + Sync>,
+}
+
+#[derive(Deserialize, Hash, Eq, PartialEq)]
+pub enum ValidateMonstersQueryKeys {
+    #[serde(rename = "monster")]
+    Monster,
+    #[serde(rename = "kind")]
+    Kind,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ValidateMonstersResponse {
+    checked: usize,
+    anomalies: Vec<Anomaly>,
+}
+
+pub async fn validate_monsters(
+    Query(params): Query<HashMap<ValidateMonstersQueryKeys, String>>,
+    State(dependencies): State<ValidateMonstersDependencies>,
+) -> Result<Json<ValidateMonstersResponse>, (StatusCode, String)> {
+    let kind = params
+        .get(&ValidateMonstersQueryKeys::Kind)
+        .map(|kind| kind.parse::<AnomalyKind>())
+        .transpose()
+        .map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+    let monsters = dependencies
+        .monsters
+        .iter()
+        .filter(|monster| {
+            params
+                .get(&ValidateMonstersQueryKeys::Monster)
+                .is_none_or(|name| monster.name.eq_ignore_ascii_case(name))
+        })
+        .collect::<Vec<_>>();
+    let anomalies = monsters
+        .iter()
+        .flat_map(|monster| dependencies.monster_validator.validate(monster))
+        .filter(|anomaly| kind.is_none_or(|kind| anomaly.kind == kind))
+        .collect();
+    Ok(Json(ValidateMonstersResponse {
+        checked: monsters.len(),
+        anomalies,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derived::derived_stats_calculator::DerivedStatsCalculatorImpl;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::load_from_json::load_from_json;
+    use crate::validation::monster_validator::MonsterValidatorImpl;
+
+    fn get_dependencies() -> ValidateMonstersDependencies {
+        ValidateMonstersDependencies {
+            monsters: Arc::new(load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH)),
+            monster_validator: Arc::new(MonsterValidatorImpl::new(Arc::new(
+                DerivedStatsCalculatorImpl::default(),
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_monsters_filters() {
+        let dependencies = get_dependencies();
+
+        let result = validate_monsters(
+            Query(HashMap::from([
+                (ValidateMonstersQueryKeys::Monster, "night hag".to_string()),
+                (ValidateMonstersQueryKeys::Kind, "inconsistent".to_string()),
+            ])),
+            State(dependencies.clone()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(result.checked, 1);
+        assert_eq!(result.anomalies.len(), 4);
+        assert!(result.anomalies.iter().all(|anomaly| {
+            anomaly.monster == "Night Hag" && anomaly.kind == AnomalyKind::Inconsistent
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_validate_monsters_all() {
+        let dependencies = get_dependencies();
+
+        let result = validate_monsters(Query(HashMap::new()), State(dependencies.clone()))
+            .await
+            .unwrap()
+            .0;
+
+        assert_eq!(result.checked, dependencies.monsters.len());
+    }
+
+    #[tokio::test]
+    async fn test_validate_monsters_invalid_kind() {
+        let result = validate_monsters(
+            Query(HashMap::from([(
+                ValidateMonstersQueryKeys::Kind,
+                "typo".to_string(),
+            )])),
+            State(get_dependencies()),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+}
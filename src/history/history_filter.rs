@@ -0,0 +1,16 @@
+use crate::history::{HistoryEntry, HistoryFilter};
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        self.kind.is_none_or(|kind| entry.record.kind == kind)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+            && self.monster.as_ref().is_none_or(|monster| {
+                entry
+                    .record
+                    .monster
+                    .as_ref()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(monster))
+            })
+    }
+}
@@ -0,0 +1,136 @@
+use crate::dice::Die;
+use crate::history::{HistoryEntry, HistoryStats, RollKind};
+use std::collections::BTreeMap;
+
+impl HistoryStats {
+    pub fn from_entries(entries: &[HistoryEntry]) -> Self {
+        let mut by_kind = BTreeMap::new();
+        let mut by_monster = BTreeMap::new();
+        for entry in entries {
+            *by_kind.entry(entry.record.kind).or_insert(0) += 1;
+            if let Some(monster) = &entry.record.monster {
+                *by_monster.entry(monster.clone()).or_insert(0) += 1;
+            }
+        }
+        let natural_d20s = entries
+            .iter()
+            .filter(|entry| {
+                matches!(
+                    entry.record.kind,
+                    RollKind::Save | RollKind::Skill | RollKind::Ability | RollKind::Attack
+                )
+            })
+            .flat_map(|entry| {
+                entry
+                    .record
+                    .rolls
+                    .iter()
+                    .find(|roll| roll.die == Die::D20 && roll.kept)
+            })
+            .map(|roll| roll.value)
+            .collect::<Vec<i32>>();
+        Self {
+            rolls: entries.len(),
+            by_kind,
+            by_monster,
+            natural_twenties: natural_d20s.iter().filter(|value| **value == 20).count(),
+            natural_ones: natural_d20s.iter().filter(|value| **value == 1).count(),
+            mean_result: match entries.len() {
+                0 => None,
+                count => Some(
+                    entries
+                        .iter()
+                        .map(|entry| entry.record.result as f64)
+                        .sum::<f64>()
+                        / count as f64,
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::Roll;
+    use crate::history::RollRecord;
+
+    fn entry(kind: RollKind, monster: Option<&str>, rolls: Vec<Roll>, result: i32) -> HistoryEntry {
+        HistoryEntry {
+            id: 0,
+            timestamp: 0,
+            record: RollRecord {
+                kind,
+                endpoint: "/v1/dice/roll/1d20".to_string(),
+                monster: monster.map(|monster| monster.to_string()),
+                expression: "1d20".to_string(),
+                result,
+                rolls,
+                seed: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_stats_from_entries() {
+        let stats = HistoryStats::from_entries(&[
+            entry(RollKind::Dice, None, vec![Roll::new(Die::D20, 20)], 20),
+            entry(
+                RollKind::Save,
+                Some("Goblin"),
+                vec![Roll::new(Die::D20, 1), Roll::new(Die::Raw, 2)],
+                3,
+            ),
+            entry(
+                RollKind::Save,
+                Some("Goblin"),
+                vec![Roll::new(Die::D6, 1), Roll::new(Die::D20, 7)],
+                8,
+            ),
+        ]);
+
+        assert_eq!(stats.rolls, 3);
+        assert_eq!(stats.by_kind.get(&RollKind::Save), Some(&2));
+        assert_eq!(stats.by_monster.get("Goblin"), Some(&2));
+        assert_eq!(stats.natural_twenties, 0);
+        assert_eq!(stats.natural_ones, 1);
+        assert_eq!(stats.mean_result, Some(31.0 / 3.0));
+    }
+
+    #[test]
+    fn test_stats_count_only_kept_check_d20() {
+        let mut dropped = Roll::new(Die::D20, 20);
+        dropped.kept = false;
+        let stats = HistoryStats::from_entries(&[
+            entry(
+                RollKind::Attack,
+                Some("Goblin"),
+                vec![dropped, Roll::new(Die::D20, 1), Roll::new(Die::Raw, 4)],
+                5,
+            ),
+            entry(
+                RollKind::Skill,
+                Some("Goblin"),
+                vec![Roll::new(Die::D20, 20), Roll::new(Die::D20, 1)],
+                20,
+            ),
+            entry(
+                RollKind::Damage,
+                Some("Goblin"),
+                vec![Roll::new(Die::D20, 20)],
+                20,
+            ),
+        ]);
+
+        assert_eq!(stats.natural_twenties, 1);
+        assert_eq!(stats.natural_ones, 1);
+    }
+
+    #[test]
+    fn test_stats_without_entries() {
+        let stats = HistoryStats::from_entries(&[]);
+
+        assert_eq!(stats.rolls, 0);
+        assert_eq!(stats.mean_result, None);
+    }
+}
@@ -0,0 +1,72 @@
+pub mod history_filter;
+pub mod history_stats;
+pub mod roll_journal;
+pub mod roll_kind;
+
+use crate::dice::Roll;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum RollKind {
+    #[serde(rename = "dice")]
+    Dice,
+    #[serde(rename = "save")]
+    Save,
+    #[serde(rename = "skill")]
+    Skill,
+    #[serde(rename = "ability")]
+    Ability,
+    #[serde(rename = "attack")]
+    Attack,
+    #[serde(rename = "damage")]
+    Damage,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct RollRecord {
+    pub kind: RollKind,
+    pub endpoint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monster: Option<String>,
+    pub expression: String,
+    pub result: i32,
+    pub rolls: Vec<Roll>,
+    pub seed: u64,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct HistoryEntry {
+    pub id: u64,
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub record: RollRecord,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct HistoryFilter {
+    pub monster: Option<String>,
+    pub kind: Option<RollKind>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct HistoryStats {
+    pub rolls: usize,
+    #[serde(rename = "byKind")]
+    pub by_kind: BTreeMap<RollKind, usize>,
+    #[serde(rename = "byMonster")]
+    pub by_monster: BTreeMap<String, usize>,
+    #[serde(rename = "naturalTwenties")]
+    pub natural_twenties: usize,
+    #[serde(rename = "naturalOnes")]
+    pub natural_ones: usize,
+    #[serde(rename = "meanResult")]
+    pub mean_result: Option<f64>,
+}
+
+pub trait RollJournal {
+    fn record(&self, record: RollRecord) -> HistoryEntry;
+    fn entries(&self, filter: &HistoryFilter) -> Vec<HistoryEntry>;
+}
@@ -0,0 +1,183 @@
+use crate::history::{HistoryEntry, HistoryFilter, RollJournal, RollRecord};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions, create_dir_all};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_ENTRIES: usize = 10_000;
+
+struct Journal {
+    entries: VecDeque<HistoryEntry>,
+    next_id: u64,
+    log: Option<File>,
+}
+
+pub struct RollJournalImpl {
+    max_entries: usize,
+    journal: Mutex<Journal>,
+}
+
+impl RollJournalImpl {
+    fn new(max_entries: usize, log: Option<File>) -> Self {
+        Self {
+            max_entries,
+            journal: Mutex::new(Journal {
+                entries: VecDeque::new(),
+                next_id: 1,
+                log,
+            }),
+        }
+    }
+
+    pub fn default() -> Self {
+        Self::new(MAX_ENTRIES, None)
+    }
+
+    pub fn with_log(path: &str) -> Result<Self, String> {
+        if let Some(parent) = Path::new(path).parent() {
+            create_dir_all(parent).map_err(|error| error.to_string())?;
+        }
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| error.to_string())?;
+        Ok(Self::new(MAX_ENTRIES, Some(log)))
+    }
+}
+
+impl RollJournal for RollJournalImpl {
+    fn record(&self, record: RollRecord) -> HistoryEntry {
+        let mut journal = self.journal.lock().unwrap();
+        let entry = HistoryEntry {
+            id: journal.next_id,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            record,
+        };
+        journal.next_id += 1;
+        if let Some(log) = journal.log.as_mut() {
+            let written = serde_json::to_string(&entry)
+                .map_err(|error| error.to_string())
+                .and_then(|line| writeln!(log, "{line}").map_err(|error| error.to_string()));
+            if let Err(error) = written {
+                tracing::warn!(
+                    "Failed to append roll {} to the history log: {error}",
+                    entry.id
+                );
+            }
+        }
+        journal.entries.push_back(entry.clone());
+        while journal.entries.len() > self.max_entries {
+            journal.entries.pop_front();
+        }
+        entry
+    }
+
+    fn entries(&self, filter: &HistoryFilter) -> Vec<HistoryEntry> {
+        self.journal
+            .lock()
+            .unwrap()
+            .entries
+            .iter()
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::{Die, Roll};
+    use crate::history::RollKind;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    fn record(kind: RollKind, monster: Option<&str>) -> RollRecord {
+        RollRecord {
+            kind,
+            endpoint: "/v1/dice/roll/1d20".to_string(),
+            monster: monster.map(|monster| monster.to_string()),
+            expression: "1d20".to_string(),
+            result: 12,
+            rolls: vec![Roll::new(Die::D20, 12)],
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn test_record_assigns_ids() {
+        let journal = RollJournalImpl::default();
+
+        let first = journal.record(record(RollKind::Dice, None));
+        let second = journal.record(record(RollKind::Dice, None));
+
+        assert_eq!(first.id, 1);
+        assert_eq!(second.id, 2);
+        assert!(second.timestamp >= first.timestamp);
+    }
+
+    #[test]
+    fn test_entries_filtered() {
+        let journal = RollJournalImpl::default();
+        journal.record(record(RollKind::Dice, None));
+        journal.record(record(RollKind::Save, Some("Goblin")));
+        journal.record(record(RollKind::Damage, Some("Goblin")));
+        journal.record(record(RollKind::Save, Some("Aboleth")));
+
+        let goblin = journal.entries(&HistoryFilter {
+            monster: Some("goblin".to_string()),
+            ..HistoryFilter::default()
+        });
+        let saves = journal.entries(&HistoryFilter {
+            kind: Some(RollKind::Save),
+            ..HistoryFilter::default()
+        });
+        let future = journal.entries(&HistoryFilter {
+            since: Some(u64::MAX),
+            ..HistoryFilter::default()
+        });
+
+        assert_eq!(goblin.len(), 2);
+        assert_eq!(saves.len(), 2);
+        assert!(future.is_empty());
+        assert_eq!(journal.entries(&HistoryFilter::default()).len(), 4);
+    }
+
+    #[test]
+    fn test_entries_capped() {
+        let journal = RollJournalImpl::new(2, None);
+        journal.record(record(RollKind::Dice, None));
+        journal.record(record(RollKind::Dice, None));
+        journal.record(record(RollKind::Dice, None));
+
+        let entries = journal.entries(&HistoryFilter::default());
+
+        assert_eq!(
+            entries.iter().map(|entry| entry.id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn test_log_appends_json_lines() {
+        let path = temp_dir().join("dm-tool-history.jsonl");
+        let _ = remove_file(&path);
+        let journal = RollJournalImpl::with_log(&path.to_string_lossy()).unwrap();
+
+        journal.record(record(RollKind::Dice, None));
+        journal.record(record(RollKind::Save, Some("Goblin")));
+
+        let lines = read_to_string(&path).unwrap();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let second = serde_json::from_str::<serde_json::Value>(lines[1]).unwrap();
+        assert_eq!(second["id"], 2);
+        assert_eq!(second["kind"], "save");
+        assert_eq!(second["monster"], "Goblin");
+    }
+}
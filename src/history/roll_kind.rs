@@ -0,0 +1,29 @@
+use crate::history::RollKind;
+use std::str::FromStr;
+
+impl FromStr for RollKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_lowercase().as_str() {
+            "dice" => Ok(RollKind::Dice),
+            "save" => Ok(RollKind::Save),
+            "skill" => Ok(RollKind::Skill),
+            "ability" => Ok(RollKind::Ability),
+            "attack" => Ok(RollKind::Attack),
+            "damage" => Ok(RollKind::Damage),
+            _ => Err(format!("Unknown roll kind `{kind}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_roll_kind() {
+        assert_eq!("Damage".parse::<RollKind>(), Ok(RollKind::Damage));
+        assert!("fumble".parse::<RollKind>().is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use crate::macros::{Macro, MacroError, MacroStore};
+use crate::utilities::MACROS_JSON_PATH;
+use crate::utilities::load_from_json::load_from_json;
+use crate::utilities::save_to_json::save_to_json;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+pub struct MacroStoreImpl {
+    path: String,
+    macros: RwLock<BTreeMap<String, String>>,
+}
+
+impl MacroStoreImpl {
+    fn new(path: &str, macros: Vec<Macro>) -> Self {
+        Self {
+            path: path.to_string(),
+            macros: RwLock::new(
+                macros
+                    .into_iter()
+                    .map(|roll_macro| (roll_macro.name, roll_macro.expression))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        let macros = match Path::new(path).exists() {
+            true => load_from_json::<Vec<Macro>>(path),
+            false => vec![],
+        };
+        Self::new(path, macros)
+    }
+
+    pub fn default() -> Self {
+        Self::load(MACROS_JSON_PATH)
+    }
+
+    fn persist(&self, macros: &BTreeMap<String, String>) -> Result<(), String> {
+        save_to_json(&self.path, &to_macros(macros))
+    }
+}
+
+impl MacroStore for MacroStoreImpl {
+    fn list(&self) -> Vec<Macro> {
+        to_macros(&self.macros.read().unwrap())
+    }
+
+    fn get(&self, name: &str) -> Option<Macro> {
+        let name = name.to_lowercase();
+        self.macros
+            .read()
+            .unwrap()
+            .get(&name)
+            .map(|expression| Macro {
+                name,
+                expression: expression.clone(),
+            })
+    }
+
+    fn save(
+        &self,
+        roll_macro: Macro,
+        validate: &dyn Fn(&Macro, &(dyn MacroStore + Send + Sync)) -> Result<(), String>,
+    ) -> Result<Macro, MacroError> {
+        if !is_valid_macro_name(&roll_macro.name) {
+            return Err(MacroError::Invalid(
+                "Macro names may only contain letters, digits and underscores".to_string(),
+            ));
+        }
+        let roll_macro = Macro {
+            name: roll_macro.name.to_lowercase(),
+            ..roll_macro
+        };
+        let mut macros = self.macros.write().unwrap();
+        let mut saved = macros.clone();
+        saved.insert(roll_macro.name.clone(), roll_macro.expression.clone());
+        validate(
+            &roll_macro,
+            &MacroStoreImpl::new(&self.path, to_macros(&saved)),
+        )
+        .map_err(MacroError::Invalid)?;
+        self.persist(&saved).map_err(MacroError::Storage)?;
+        *macros = saved;
+        Ok(roll_macro)
+    }
+
+    fn delete(&self, name: &str) -> Result<Option<Macro>, String> {
+        let name = name.to_lowercase();
+        let mut macros = self.macros.write().unwrap();
+        let Some(expression) = macros.remove(&name) else {
+            return Ok(None);
+        };
+        if let Err(error) = self.persist(&macros) {
+            macros.insert(name, expression);
+            return Err(error);
+        }
+        Ok(Some(Macro { name, expression }))
+    }
+}
+
+pub fn is_valid_macro_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+fn to_macros(macros: &BTreeMap<String, String>) -> Vec<Macro> {
+    macros
+        .iter()
+        .map(|(name, expression)| Macro {
+            name: name.clone(),
+            expression: expression.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    fn temporary_path(name: &str) -> String {
+        let path = temp_dir().join(format!("dm-tool-macros-{name}.json"));
+        let _ = std::fs::remove_file(&path);
+        path.to_string_lossy().to_string()
+    }
+
+    fn longsword() -> Macro {
+        Macro {
+            name: "Longsword".to_string(),
+            expression: "1d20+7".to_string(),
+        }
+    }
+
+    fn accept(_: &Macro, _: &(dyn MacroStore + Send + Sync)) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let store = MacroStoreImpl::load(&temporary_path("missing"));
+
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let path = temporary_path("reload");
+        let store = MacroStoreImpl::load(&path);
+
+        let saved = store.save(longsword(), &accept).unwrap();
+
+        assert_eq!(saved.name, "longsword");
+        assert_eq!(store.get("LONGSWORD"), Some(saved.clone()));
+        assert_eq!(MacroStoreImpl::load(&path).list(), vec![saved]);
+    }
+
+    #[test]
+    fn test_delete() {
+        let path = temporary_path("delete");
+        let store = MacroStoreImpl::load(&path);
+        store.save(longsword(), &accept).unwrap();
+
+        assert!(store.delete("longsword").unwrap().is_some());
+        assert_eq!(store.delete("longsword"), Ok(None));
+        assert!(MacroStoreImpl::load(&path).list().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_invalid_name() {
+        let store = MacroStoreImpl::load(&temporary_path("invalid"));
+
+        let result = store.save(
+            Macro {
+                name: "long sword".to_string(),
+                ..longsword()
+            },
+            &accept,
+        );
+
+        assert!(result.is_err());
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_validates_against_saved_macros() {
+        let path = temporary_path("validate");
+        let store = MacroStoreImpl::load(&path);
+        store.save(longsword(), &accept).unwrap();
+
+        let result = store.save(
+            Macro {
+                name: "smite".to_string(),
+                expression: "@longsword+2d8".to_string(),
+            },
+            &|roll_macro, macros| {
+                assert_eq!(macros.get("smite"), Some(roll_macro.clone()));
+                assert!(macros.get("longsword").is_some());
+                Err("Rejected".to_string())
+            },
+        );
+
+        assert_eq!(result, Err(MacroError::Invalid("Rejected".to_string())));
+        assert_eq!(store.get("smite"), None);
+        assert_eq!(MacroStoreImpl::load(&path).list().len(), 1);
+    }
+}
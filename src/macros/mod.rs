@@ -0,0 +1,26 @@
+pub mod macro_store;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Macro {
+    pub name: String,
+    pub expression: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MacroError {
+    Invalid(String),
+    Storage(String),
+}
+
+pub trait MacroStore {
+    fn list(&self) -> Vec<Macro>;
+    fn get(&self, name: &str) -> Option<Macro>;
+    fn save(
+        &self,
+        roll_macro: Macro,
+        validate: &dyn Fn(&Macro, &(dyn MacroStore + Send + Sync)) -> Result<(), String>,
+    ) -> Result<Macro, MacroError>;
+    fn delete(&self, name: &str) -> Result<Option<Macro>, String>;
+}
@@ -1,16 +1,43 @@
+mod criticals;
+mod derived;
 mod dice;
 mod handlers;
+mod history;
+mod macros;
 mod monsters;
 mod stats;
+mod suggestions;
 mod utilities;
+mod validation;
 
-use crate::dice::DiceRoller;
+use crate::criticals::critical_strategy::build_critical_strategies;
+use crate::criticals::{CriticalRule, CriticalStrategy};
+use crate::derived::DerivedStatsCalculator;
+use crate::derived::derived_stats_calculator::DerivedStatsCalculatorImpl;
+use crate::dice::dice_distribution_calculator::DiceDistributionCalculatorImpl;
 use crate::dice::dice_roller::DiceRollerImpl;
 use crate::dice::die_roller::DieRollerImpl;
+use crate::dice::{DiceDistributionCalculator, DiceRoller};
+use crate::handlers::apply_damage::ApplyDamageDependencies;
+use crate::handlers::dice_distribution::DiceDistributionDependencies;
+use crate::handlers::get_derived_stats::DerivedStatsDependencies;
 use crate::handlers::get_monster::GetMonsterDependencies;
 use crate::handlers::list_monsters::ListMonstersDependencies;
+use crate::handlers::manage_macros::MacroDependencies;
+use crate::handlers::resolve_attack::ResolveAttackDependencies;
 use crate::handlers::roll_attack::RollAttackDependencies;
-use crate::handlers::{get_monster, list_dice, list_monsters, roll_attack, roll_stat};
+use crate::handlers::roll_history::RollHistoryDependencies;
+use crate::handlers::suggest_monsters::SuggestMonstersDependencies;
+use crate::handlers::validate_monsters::ValidateMonstersDependencies;
+use crate::handlers::{
+    apply_damage, concentration_check, dice_distribution, get_derived_stats, get_monster,
+    list_dice, list_monsters, manage_macros, resolve_attack, roll_attack, roll_group, roll_history,
+    roll_stat, suggest_monsters, validate_monsters,
+};
+use crate::history::roll_journal::RollJournalImpl;
+use crate::history::{RollJournal, RollKind};
+use crate::macros::MacroStore;
+use crate::macros::macro_store::MacroStoreImpl;
 use crate::monsters::Monster;
 use crate::monsters::search::MonsterSearch;
 use crate::stats::modifier_extractor::{
@@ -19,17 +46,23 @@ use crate::stats::modifier_extractor::{
 };
 use crate::stats::stat_roller::StatRollerImpl;
 use crate::stats::{SkillType, StatRoller, StatType};
-use crate::utilities::MONSTERS_JSON_PATH;
+use crate::suggestions::MonsterSuggester;
+use crate::suggestions::monster_suggester::MonsterSuggesterImpl;
 use crate::utilities::index::vec_to_map;
 use crate::utilities::load_from_json::load_from_json;
+use crate::utilities::{CRITICAL_RULE_VARIABLE, HISTORY_LOG_VARIABLE, MONSTERS_JSON_PATH};
+use crate::validation::MonsterValidator;
+use crate::validation::monster_validator::MonsterValidatorImpl;
 use axum::Router;
-use axum::routing::get;
+use axum::routing::{get, post};
 use dice::DiceExpressionParser;
 use dice::dice_expression_parser::DiceExpressionParserImpl;
 use handlers::MonsterRollerDependencies;
 use handlers::roll_dice;
 use handlers::roll_dice::RollDiceHandlerDependencies;
+use handlers::roll_dice_batch;
 use std::collections::HashMap;
+use std::env;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tower_http::trace::TraceLayer;
@@ -47,6 +80,26 @@ async fn main() {
                 .with_state(RollDiceHandlerDependencies {
                     dice_expression_parser: dependencies.dice_expression_parser.clone(),
                     dice_roller: dependencies.dice_roller.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/dice/roll",
+            post(roll_dice_batch::roll_dice_batch)
+                .with_state(RollDiceHandlerDependencies {
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    dice_roller: dependencies.dice_roller.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/dice/distribution/{roll_expression}",
+            get(dice_distribution::dice_distribution)
+                .with_state(DiceDistributionDependencies {
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    dice_distribution_calculator: dependencies.dice_distribution_calculator.clone(),
                 })
                 .layer(TraceLayer::new_for_http()),
         )
@@ -54,6 +107,43 @@ async fn main() {
             "/v1/dice/list",
             get(list_dice::list_dice).layer(TraceLayer::new_for_http()),
         )
+        .route(
+            "/v1/admin/validate",
+            get(validate_monsters::validate_monsters)
+                .with_state(ValidateMonstersDependencies {
+                    monsters: dependencies.monsters.clone(),
+                    monster_validator: dependencies.monster_validator.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/damage/apply",
+            post(apply_damage::apply_damage)
+                .with_state(ApplyDamageDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/macros",
+            get(manage_macros::list_macros)
+                .with_state(MacroDependencies {
+                    macro_store: dependencies.macro_store.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/macros/{macro_name}",
+            get(manage_macros::get_macro)
+                .put(manage_macros::put_macro)
+                .delete(manage_macros::delete_macro)
+                .with_state(MacroDependencies {
+                    macro_store: dependencies.macro_store.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
         .route(
             "/v1/monsters",
             get(list_monsters::list_monsters)
@@ -63,6 +153,14 @@ async fn main() {
                 })
                 .layer(TraceLayer::new_for_http()),
         )
+        .route(
+            "/v1/monsters/suggest",
+            get(suggest_monsters::suggest_monsters)
+                .with_state(SuggestMonstersDependencies {
+                    monster_suggester: dependencies.monster_suggester.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
         .route(
             "/v1/monsters/{monster_name}",
             get(get_monster::get_monster)
@@ -71,13 +169,38 @@ async fn main() {
                 })
                 .layer(TraceLayer::new_for_http()),
         )
+        .route(
+            "/v1/monsters/{monster_name}/derived",
+            get(get_derived_stats::get_derived_stats)
+                .with_state(DerivedStatsDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                    derived_stats_calculator: dependencies.derived_stats_calculator.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
         .route(
             "/v1/monsters/{monster_name}/roll/throw/{stat}",
             get(roll_stat::roll_stat)
                 .with_state(MonsterRollerDependencies {
                     monster_map: dependencies.monster_map.clone(),
                     stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    modifier_extractor: dependencies.saving_throw_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Save,
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/monsters/{monster_name}/concentration",
+            post(concentration_check::concentration_check)
+                .with_state(MonsterRollerDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                    stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
                     modifier_extractor: dependencies.saving_throw_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Save,
                 })
                 .layer(TraceLayer::new_for_http()),
         )
@@ -87,7 +210,10 @@ async fn main() {
                 .with_state(MonsterRollerDependencies {
                     monster_map: dependencies.monster_map.clone(),
                     stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
                     modifier_extractor: dependencies.skill_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Skill,
                 })
                 .layer(TraceLayer::new_for_http()),
         )
@@ -97,7 +223,10 @@ async fn main() {
                 .with_state(MonsterRollerDependencies {
                     monster_map: dependencies.monster_map.clone(),
                     stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
                     modifier_extractor: dependencies.stat_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Ability,
                 })
                 .layer(TraceLayer::new_for_http()),
         )
@@ -107,7 +236,10 @@ async fn main() {
                 .with_state(MonsterRollerDependencies {
                     monster_map: dependencies.monster_map.clone(),
                     stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
                     modifier_extractor: dependencies.attack_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Attack,
                 })
                 .layer(TraceLayer::new_for_http()),
         )
@@ -118,6 +250,78 @@ async fn main() {
                     dice_expression_parser: dependencies.dice_expression_parser.clone(),
                     dice_roller: dependencies.dice_roller.clone(),
                     monster_map: dependencies.monster_map.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    critical_strategies: dependencies.critical_strategies.clone(),
+                    critical_rule: dependencies.critical_rule,
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/monsters/{monster_name}/attack/{index}",
+            get(resolve_attack::resolve_attack)
+                .with_state(ResolveAttackDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                    stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    dice_roller: dependencies.dice_roller.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    critical_strategies: dependencies.critical_strategies.clone(),
+                    critical_rule: dependencies.critical_rule,
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/group/roll/throw/{stat}",
+            post(roll_group::roll_group)
+                .with_state(MonsterRollerDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                    stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    modifier_extractor: dependencies.saving_throw_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Save,
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/group/roll/skill/{skill}",
+            post(roll_group::roll_group)
+                .with_state(MonsterRollerDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                    stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    modifier_extractor: dependencies.skill_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Skill,
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/group/roll/stat/{stat}",
+            post(roll_group::roll_group)
+                .with_state(MonsterRollerDependencies {
+                    monster_map: dependencies.monster_map.clone(),
+                    stats_roller: dependencies.stat_roller.clone(),
+                    dice_expression_parser: dependencies.dice_expression_parser.clone(),
+                    modifier_extractor: dependencies.stat_modifier_extractor.clone(),
+                    roll_journal: dependencies.roll_journal.clone(),
+                    roll_kind: RollKind::Ability,
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/history",
+            get(roll_history::list_history)
+                .with_state(RollHistoryDependencies {
+                    roll_journal: dependencies.roll_journal.clone(),
+                })
+                .layer(TraceLayer::new_for_http()),
+        )
+        .route(
+            "/v1/history/stats",
+            get(roll_history::history_stats)
+                .with_state(RollHistoryDependencies {
+                    roll_journal: dependencies.roll_journal.clone(),
                 })
                 .layer(TraceLayer::new_for_http()),
         );
@@ -126,12 +330,20 @@ async fn main() {
 }
 
 struct Dependencies {
+    macro_store: Arc<dyn MacroStore + Send + Sync>,
     dice_expression_parser: Arc<dyn DiceExpressionParser + Send + Sync>,
     dice_roller: Arc<dyn DiceRoller + Send + Sync>,
+    dice_distribution_calculator: Arc<dyn DiceDistributionCalculator + Send + Sync>,
+    roll_journal: Arc<dyn RollJournal + Send + Sync>,
+    critical_strategies: Arc<HashMap<CriticalRule, Arc<dyn CriticalStrategy + Send + Sync>>>,
+    critical_rule: CriticalRule,
     stat_roller: Arc<dyn StatRoller + Send + Sync>,
     monsters: Arc<Vec<Monster>>,
     monster_search: Arc<MonsterSearch>,
+    monster_suggester: Arc<dyn MonsterSuggester + Send + Sync>,
     monster_map: Arc<HashMap<String, Monster>>,
+    derived_stats_calculator: Arc<dyn DerivedStatsCalculator + Send + Sync>,
+    monster_validator: Arc<dyn MonsterValidator + Send + Sync>,
     saving_throw_modifier_extractor: Arc<ModifierExtractor<StatType>>,
     skill_modifier_extractor: Arc<ModifierExtractor<SkillType>>,
     stat_modifier_extractor: Arc<ModifierExtractor<StatType>>,
@@ -139,27 +351,77 @@ struct Dependencies {
 }
 
 fn build_dependencies() -> Dependencies {
-    let dice_expression_parser = Arc::new(DiceExpressionParserImpl::default());
+    let macro_store = Arc::new(MacroStoreImpl::default());
+    let dice_expression_parser =
+        Arc::new(DiceExpressionParserImpl::default().with_macros(macro_store.clone()));
     let die_roller = Arc::new(DieRollerImpl::default());
     let dice_roller = Arc::new(DiceRollerImpl::new(die_roller.clone()));
+    let dice_distribution_calculator = Arc::new(DiceDistributionCalculatorImpl::default());
+    let roll_journal = Arc::new(match env::var(HISTORY_LOG_VARIABLE) {
+        Ok(path) => RollJournalImpl::with_log(&path).unwrap(),
+        Err(_) => RollJournalImpl::default(),
+    });
+    let critical_strategies = Arc::new(build_critical_strategies());
+    let critical_rule = match env::var(CRITICAL_RULE_VARIABLE) {
+        Ok(rule) => rule.parse::<CriticalRule>().unwrap(),
+        Err(_) => CriticalRule::DoubleDice,
+    };
     let stat_roller = Arc::new(StatRollerImpl::new(dice_roller.clone()));
     let monsters = Arc::new(load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH));
     let monster_map = Arc::new(vec_to_map(&monsters, |monster| monster.name.to_lowercase()));
     let monster_search = Arc::new(MonsterSearch::from_map(monster_map.clone()));
+    let monster_suggester = Arc::new(MonsterSuggesterImpl::new(&monsters));
+    let derived_stats_calculator = Arc::new(DerivedStatsCalculatorImpl::default());
+    let monster_validator = Arc::new(MonsterValidatorImpl::new(derived_stats_calculator.clone()));
+    report_anomalies(monster_validator.as_ref(), &monsters);
     let saving_throw_modifier_extractor = Arc::new(build_saving_throw_modifier_extractor());
     let skill_modifier_extractor = Arc::new(build_skill_modifier_extractor());
     let stat_modifier_extractor = Arc::new(build_stat_modifier_extractor());
     let attack_modifier_extractor = Arc::new(build_attack_modifier_extractor());
     Dependencies {
+        macro_store,
         dice_expression_parser,
         dice_roller,
+        dice_distribution_calculator,
+        roll_journal,
+        critical_strategies,
+        critical_rule,
         stat_roller,
         monsters,
         monster_search,
+        monster_suggester,
         monster_map,
+        derived_stats_calculator,
+        monster_validator,
         saving_throw_modifier_extractor,
         skill_modifier_extractor,
         stat_modifier_extractor,
         attack_modifier_extractor,
     }
 }
+
+fn report_anomalies(
+    monster_validator: &(dyn MonsterValidator + Send + Sync),
+    monsters: &[Monster],
+) {
+    let anomalies = monsters
+        .iter()
+        .flat_map(|monster| monster_validator.validate(monster))
+        .collect::<Vec<_>>();
+    for anomaly in &anomalies {
+        tracing::debug!(
+            "{} has {:?} {} of {}, expected one of {:?}",
+            anomaly.monster,
+            anomaly.kind,
+            anomaly.field,
+            anomaly.value,
+            anomaly.expected
+        );
+    }
+    if !anomalies.is_empty() {
+        tracing::warn!(
+            "Found {} monster data anomalies, see /v1/admin/validate",
+            anomalies.len()
+        );
+    }
+}
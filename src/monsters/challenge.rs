@@ -0,0 +1,35 @@
+use crate::monsters::Challenge;
+
+impl Challenge {
+    pub fn value(&self) -> Option<f64> {
+        parse_rating(&self.rating)
+    }
+}
+
+pub fn parse_rating(rating: &str) -> Option<f64> {
+    match rating.trim().split_once('/') {
+        None => rating.trim().parse::<f64>().ok(),
+        Some((numerator, denominator)) => {
+            let numerator = numerator.trim().parse::<f64>().ok()?;
+            let denominator = denominator.trim().parse::<f64>().ok()?;
+            (denominator != 0.0).then(|| numerator / denominator)
+        }
+    }
+    .filter(|value| value.is_finite() && *value >= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rating() {
+        assert_eq!(parse_rating("1/4"), Some(0.25));
+        assert_eq!(parse_rating("1/8"), Some(0.125));
+        assert_eq!(parse_rating("17"), Some(17.0));
+        assert_eq!(parse_rating("0"), Some(0.0));
+        assert_eq!(parse_rating("1/0"), None);
+        assert_eq!(parse_rating("-1"), None);
+        assert_eq!(parse_rating("tough"), None);
+    }
+}
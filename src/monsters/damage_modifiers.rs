@@ -0,0 +1,103 @@
+use crate::monsters::{DamageAdjustment, DamageModifiers, DamageType};
+
+impl DamageModifiers {
+    pub fn merge(&self, other: &DamageModifiers) -> DamageModifiers {
+        let union = |first: &[DamageType], second: &[DamageType]| {
+            first
+                .iter()
+                .chain(second)
+                .fold(vec![], |mut types, damage_type| {
+                    if !types.contains(damage_type) {
+                        types.push(damage_type.clone());
+                    }
+                    types
+                })
+        };
+        DamageModifiers {
+            resistances: union(&self.resistances, &other.resistances),
+            vulnerabilities: union(&self.vulnerabilities, &other.vulnerabilities),
+            immunities: union(&self.immunities, &other.immunities),
+        }
+    }
+
+    pub fn apply(&self, damage_type: &DamageType, total: i32) -> (i32, Vec<DamageAdjustment>) {
+        if self.immunities.contains(damage_type) {
+            return (0, vec![DamageAdjustment::Immunity]);
+        }
+        let mut adjustments = vec![];
+        let mut adjusted = total;
+        if self.resistances.contains(damage_type) {
+            adjusted /= 2;
+            adjustments.push(DamageAdjustment::Resistance);
+        }
+        if self.vulnerabilities.contains(damage_type) {
+            adjusted = adjusted.saturating_mul(2);
+            adjustments.push(DamageAdjustment::Vulnerability);
+        }
+        (adjusted, adjustments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifiers() -> DamageModifiers {
+        DamageModifiers {
+            resistances: vec![DamageType::Fire, DamageType::Cold],
+            vulnerabilities: vec![DamageType::Cold, DamageType::Radiant],
+            immunities: vec![DamageType::Poison],
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let modifiers = modifiers();
+
+        assert_eq!(
+            modifiers.apply(&DamageType::Fire, 15),
+            (7, vec![DamageAdjustment::Resistance])
+        );
+        assert_eq!(
+            modifiers.apply(&DamageType::Radiant, 15),
+            (30, vec![DamageAdjustment::Vulnerability])
+        );
+        assert_eq!(
+            modifiers.apply(&DamageType::Cold, 15),
+            (
+                14,
+                vec![
+                    DamageAdjustment::Resistance,
+                    DamageAdjustment::Vulnerability
+                ]
+            )
+        );
+        assert_eq!(
+            modifiers.apply(&DamageType::Poison, 15),
+            (0, vec![DamageAdjustment::Immunity])
+        );
+        assert_eq!(modifiers.apply(&DamageType::Slashing, 15), (15, vec![]));
+    }
+
+    #[test]
+    fn test_apply_saturates() {
+        assert_eq!(
+            modifiers().apply(&DamageType::Radiant, i32::MAX),
+            (i32::MAX, vec![DamageAdjustment::Vulnerability])
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let merged = modifiers().merge(&DamageModifiers {
+            resistances: vec![DamageType::Fire, DamageType::Slashing],
+            ..DamageModifiers::default()
+        });
+
+        assert_eq!(
+            merged.resistances,
+            vec![DamageType::Fire, DamageType::Cold, DamageType::Slashing]
+        );
+        assert_eq!(merged.immunities, vec![DamageType::Poison]);
+    }
+}
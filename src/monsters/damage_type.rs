@@ -0,0 +1,36 @@
+use crate::monsters::DamageType;
+use std::str::FromStr;
+
+impl FromStr for DamageType {
+    type Err = String;
+
+    fn from_str(damage_type: &str) -> Result<Self, Self::Err> {
+        match damage_type.to_lowercase().as_str() {
+            "bludgeoning" => Ok(DamageType::Bludgeoning),
+            "piercing" => Ok(DamageType::Piercing),
+            "slashing" => Ok(DamageType::Slashing),
+            "acid" => Ok(DamageType::Acid),
+            "lightning" => Ok(DamageType::Lightning),
+            "poison" => Ok(DamageType::Poison),
+            "fire" => Ok(DamageType::Fire),
+            "cold" => Ok(DamageType::Cold),
+            "radiant" => Ok(DamageType::Radiant),
+            "necrotic" => Ok(DamageType::Necrotic),
+            "thunder" => Ok(DamageType::Thunder),
+            "force" => Ok(DamageType::Force),
+            "psychic" => Ok(DamageType::Psychic),
+            _ => Err(format!("Unknown damage type `{damage_type}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_damage_type() {
+        assert_eq!("Fire".parse::<DamageType>(), Ok(DamageType::Fire));
+        assert!("sonic".parse::<DamageType>().is_err());
+    }
+}
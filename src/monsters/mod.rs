@@ -1,5 +1,14 @@
+pub mod challenge;
+pub mod damage_modifiers;
+pub mod damage_type;
 pub mod monster;
+pub mod monster_filter;
+pub mod monster_sort;
+pub mod movement_mode;
 pub(crate) mod search;
+pub mod search_field;
+pub mod search_query;
+pub mod size;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -33,12 +42,15 @@ pub struct Monster {
     #[serde(rename = "legendaryActions")]
     pub legendary_actions: Vec<String>,
     pub reactions: Vec<String>,
+    #[serde(default)]
+    #[serde(rename = "damageModifiers")]
+    pub damage_modifiers: DamageModifiers,
     pub challenge: Challenge,
     #[serde(rename = "imageUrl")]
     pub image_url: String,
 }
 
-#[derive(Serialize, Deserialize, Display, Clone, Debug)]
+#[derive(Serialize, Deserialize, Display, Clone, Debug, PartialEq)]
 pub enum Size {
     #[serde(rename = "gargantuan")]
     Gargantuan,
@@ -64,6 +76,15 @@ pub struct Speed {
     pub hover: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MovementMode {
+    Walk,
+    Fly,
+    Swim,
+    Burrow,
+    Climb,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Stats {
     #[serde(rename = "str")]
@@ -140,7 +161,7 @@ pub enum AttackType {
     RangedSpell,
 }
 
-#[derive(Serialize, Deserialize, Display, Clone, Debug)]
+#[derive(Serialize, Deserialize, Display, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum DamageType {
     #[serde(rename = "bludgeoning")]
     Bludgeoning,
@@ -162,6 +183,32 @@ pub enum DamageType {
     Radiant,
     #[serde(rename = "necrotic")]
     Necrotic,
+    #[serde(rename = "thunder")]
+    Thunder,
+    #[serde(rename = "force")]
+    Force,
+    #[serde(rename = "psychic")]
+    Psychic,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DamageModifiers {
+    #[serde(default)]
+    pub resistances: Vec<DamageType>,
+    #[serde(default)]
+    pub vulnerabilities: Vec<DamageType>,
+    #[serde(default)]
+    pub immunities: Vec<DamageType>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum DamageAdjustment {
+    #[serde(rename = "immunity")]
+    Immunity,
+    #[serde(rename = "resistance")]
+    Resistance,
+    #[serde(rename = "vulnerability")]
+    Vulnerability,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -169,3 +216,67 @@ pub struct Challenge {
     pub rating: String,
     pub xp: i32,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SearchField {
+    Name,
+    Type,
+    Size,
+    Alignment,
+    Language,
+    Trait,
+    Action,
+    Damage,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchQuery {
+    Term {
+        field: Option<SearchField>,
+        word: String,
+    },
+    Phrase {
+        field: Option<SearchField>,
+        words: Vec<String>,
+    },
+    Not(Box<SearchQuery>),
+    And(Vec<SearchQuery>),
+    Or(Vec<SearchQuery>),
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoredMonster {
+    pub monster: Monster,
+    pub score: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortField {
+    Name,
+    Challenge,
+    Ac,
+    HitPoints,
+    Xp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MonsterSort {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MonsterFilter {
+    pub min_challenge: Option<f64>,
+    pub max_challenge: Option<f64>,
+    pub size: Option<Size>,
+    pub creature_type: Option<String>,
+    pub alignment: Vec<String>,
+    pub movement: Vec<MovementMode>,
+    pub min_ac: Option<i32>,
+    pub max_ac: Option<i32>,
+    pub min_hit_points: Option<i32>,
+    pub max_hit_points: Option<i32>,
+    pub damage_type: Option<DamageType>,
+    pub language: Option<String>,
+}
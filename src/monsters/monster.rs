@@ -1,4 +1,5 @@
 use crate::monsters::{Actions, AttackRoll, DamageRoll, Monster, Tokenize};
+use crate::utilities::text::normalize;
 use std::collections::HashSet;
 use std::iter::once;
 
@@ -11,14 +12,15 @@ impl Tokenize for Monster {
             .chain(self.languages.clone())
             .chain(self.traits.clone())
             .chain(self.actions.iter().flat_map(|action| action.tokenize()))
-            .flat_map(|token| token.split(' ').map(|s| s.to_string()).collect::<Vec<_>>())
-            .map(|token| {
-                token
-                    .chars()
-                    .filter(|ch| ch.is_alphanumeric())
-                    .collect::<String>()
-            })
-            .map(|token| token.to_lowercase())
+            .flat_map(|token| token.tokenize())
+            .collect()
+    }
+}
+
+impl Tokenize for str {
+    fn tokenize(&self) -> HashSet<String> {
+        self.split(' ')
+            .map(normalize)
             .filter(|token| !token.is_empty())
             .collect()
     }
@@ -74,4 +76,12 @@ mod tests {
         assert!(monster_token_chars.all(|ch| ch.is_alphanumeric()));
         assert!(monster_token_chars.all(|ch| ch.is_lowercase()));
     }
+
+    #[test]
+    fn tokenize_text() {
+        assert_eq!(
+            "Bite: the Dragon's  bite".tokenize(),
+            HashSet::from(["bite".to_string(), "the".to_string(), "dragons".to_string()])
+        );
+    }
 }
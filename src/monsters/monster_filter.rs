@@ -0,0 +1,126 @@
+use crate::monsters::{Monster, MonsterFilter};
+
+impl MonsterFilter {
+    pub fn matches(&self, monster: &Monster) -> bool {
+        let challenge = monster.challenge.value();
+        let alignment = monster.alignment.to_lowercase();
+        self.min_challenge
+            .is_none_or(|min| challenge.is_some_and(|challenge| challenge >= min))
+            && self
+                .max_challenge
+                .is_none_or(|max| challenge.is_some_and(|challenge| challenge <= max))
+            && self.size.as_ref().is_none_or(|size| monster.size == *size)
+            && self.creature_type.as_ref().is_none_or(|creature_type| {
+                monster.creature_type.eq_ignore_ascii_case(creature_type)
+            })
+            && self.alignment.iter().all(|component| {
+                alignment
+                    .split_whitespace()
+                    .any(|word| word.eq_ignore_ascii_case(component))
+            })
+            && self
+                .movement
+                .iter()
+                .all(|mode| mode.speed(&monster.speed) > 0)
+            && self.min_ac.is_none_or(|min| monster.ac >= min)
+            && self.max_ac.is_none_or(|max| monster.ac <= max)
+            && self
+                .min_hit_points
+                .is_none_or(|min| monster.max_hit_points >= min)
+            && self
+                .max_hit_points
+                .is_none_or(|max| monster.max_hit_points <= max)
+            && self.damage_type.as_ref().is_none_or(|damage_type| {
+                monster.actions.as_ref().is_some_and(|actions| {
+                    actions
+                        .attack_rolls
+                        .iter()
+                        .flat_map(|attack| &attack.damage)
+                        .any(|damage| damage.damage_type == *damage_type)
+                })
+            })
+            && self.language.as_ref().is_none_or(|language| {
+                let language = language.to_lowercase();
+                monster
+                    .languages
+                    .iter()
+                    .any(|known| known.to_lowercase().contains(&language))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monsters::{DamageType, MovementMode, Size};
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::load_from_json::load_from_json;
+
+    fn filtered(filter: &MonsterFilter) -> Vec<Monster> {
+        load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH)
+            .into_iter()
+            .filter(|monster| filter.matches(monster))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_filter_matches_everything() {
+        let monsters = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH);
+
+        assert_eq!(filtered(&MonsterFilter::default()).len(), monsters.len());
+    }
+
+    #[test]
+    fn test_challenge_range() {
+        let monsters = filtered(&MonsterFilter {
+            min_challenge: Some(0.25),
+            max_challenge: Some(0.5),
+            ..MonsterFilter::default()
+        });
+
+        assert!(!monsters.is_empty());
+        assert!(
+            monsters
+                .iter()
+                .all(|monster| ["1/4", "1/2"].contains(&monster.challenge.rating.as_str()))
+        );
+    }
+
+    #[test]
+    fn test_combined_filters() {
+        let monsters = filtered(&MonsterFilter {
+            size: Some(Size::Huge),
+            creature_type: Some("Dragon".to_string()),
+            alignment: vec!["chaotic".to_string(), "evil".to_string()],
+            movement: vec![MovementMode::Fly, MovementMode::Burrow],
+            min_ac: Some(18),
+            damage_type: Some(DamageType::Cold),
+            language: Some("draconic".to_string()),
+            ..MonsterFilter::default()
+        });
+
+        assert_eq!(
+            monsters
+                .iter()
+                .map(|monster| monster.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Adult White Dragon"]
+        );
+    }
+
+    #[test]
+    fn test_hit_point_range() {
+        let monsters = filtered(&MonsterFilter {
+            min_hit_points: Some(100),
+            max_hit_points: Some(120),
+            ..MonsterFilter::default()
+        });
+
+        assert!(!monsters.is_empty());
+        assert!(
+            monsters
+                .iter()
+                .all(|monster| (100..=120).contains(&monster.max_hit_points))
+        );
+    }
+}
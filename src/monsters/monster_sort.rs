@@ -0,0 +1,67 @@
+use crate::monsters::{Monster, MonsterSort, SortField};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+impl MonsterSort {
+    pub fn compare(&self, first: &Monster, second: &Monster) -> Ordering {
+        let ordering = match self.field {
+            SortField::Name => first.name.to_lowercase().cmp(&second.name.to_lowercase()),
+            SortField::Challenge => first
+                .challenge
+                .value()
+                .unwrap_or(f64::MAX)
+                .total_cmp(&second.challenge.value().unwrap_or(f64::MAX)),
+            SortField::Ac => first.ac.cmp(&second.ac),
+            SortField::HitPoints => first.max_hit_points.cmp(&second.max_hit_points),
+            SortField::Xp => first.challenge.xp.cmp(&second.challenge.xp),
+        };
+        match self.descending {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+}
+
+impl FromStr for MonsterSort {
+    type Err = String;
+
+    fn from_str(sort: &str) -> Result<Self, Self::Err> {
+        let (descending, field) = match sort.strip_prefix('-') {
+            Some(field) => (true, field),
+            None => (false, sort),
+        };
+        let field = match field.to_lowercase().as_str() {
+            "name" => SortField::Name,
+            "cr" => SortField::Challenge,
+            "ac" => SortField::Ac,
+            "hp" => SortField::HitPoints,
+            "xp" => SortField::Xp,
+            _ => return Err(format!("Unknown sort field `{field}`")),
+        };
+        Ok(MonsterSort { field, descending })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_monster_sort() {
+        assert_eq!(
+            "-CR".parse::<MonsterSort>(),
+            Ok(MonsterSort {
+                field: SortField::Challenge,
+                descending: true,
+            })
+        );
+        assert_eq!(
+            "hp".parse::<MonsterSort>(),
+            Ok(MonsterSort {
+                field: SortField::HitPoints,
+                descending: false,
+            })
+        );
+        assert!("speed".parse::<MonsterSort>().is_err());
+    }
+}
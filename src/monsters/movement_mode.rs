@@ -0,0 +1,40 @@
+use crate::monsters::{MovementMode, Speed};
+use std::str::FromStr;
+
+impl MovementMode {
+    pub fn speed(&self, speed: &Speed) -> i32 {
+        match self {
+            MovementMode::Walk => speed.walk,
+            MovementMode::Fly => speed.fly,
+            MovementMode::Swim => speed.swim,
+            MovementMode::Burrow => speed.burrow,
+            MovementMode::Climb => speed.climb,
+        }
+    }
+}
+
+impl FromStr for MovementMode {
+    type Err = String;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode.to_lowercase().as_str() {
+            "walk" => Ok(MovementMode::Walk),
+            "fly" => Ok(MovementMode::Fly),
+            "swim" => Ok(MovementMode::Swim),
+            "burrow" => Ok(MovementMode::Burrow),
+            "climb" => Ok(MovementMode::Climb),
+            _ => Err(format!("Unknown movement mode `{mode}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_movement_mode() {
+        assert_eq!("Fly".parse::<MovementMode>(), Ok(MovementMode::Fly));
+        assert!("teleport".parse::<MovementMode>().is_err());
+    }
+}
@@ -1,40 +1,309 @@
-use crate::monsters::{Monster, Tokenize};
+use crate::monsters::search_query::parse_query;
+use crate::monsters::{Monster, ScoredMonster, SearchField, SearchQuery, Tokenize};
 use crate::utilities::index::invert_index;
+use crate::utilities::text::{edit_distance, normalize, stem};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+const NAME_WEIGHT: f64 = 3.0;
+const PREFIX_WEIGHT: f64 = 0.7;
+const TYPO_WEIGHT: f64 = 0.5;
+const MIN_PREFIX_LENGTH: usize = 3;
+const MIN_TYPO_LENGTH: usize = 4;
+const DOUBLE_TYPO_LENGTH: usize = 8;
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+struct Posting {
+    name_frequency: f64,
+    body_frequency: f64,
+}
+
 pub struct MonsterSearch {
     monster_map: Arc<HashMap<String, Monster>>,
-    key_index: Arc<HashMap<String, HashSet<String>>>,
+    postings: HashMap<String, HashMap<String, Posting>>,
+    vocabulary: Vec<String>,
+    document_lengths: HashMap<String, f64>,
+    average_length: f64,
+    field_indexes: HashMap<SearchField, HashMap<String, HashSet<String>>>,
+    sequences: HashMap<String, Vec<(SearchField, Vec<String>)>>,
 }
 
 impl MonsterSearch {
     pub fn from_map(monster_map: Arc<HashMap<String, Monster>>) -> MonsterSearch {
-        let tokens_map: HashMap<String, HashSet<String>> = monster_map
+        let body_terms: HashMap<String, HashSet<String>> = monster_map
             .iter()
-            .map(|k| (k.0.clone(), k.1.tokenize()))
+            .map(|(key, monster)| {
+                (
+                    key.clone(),
+                    monster.tokenize().iter().map(|token| stem(token)).collect(),
+                )
+            })
+            .collect();
+        let mut postings: HashMap<String, HashMap<String, Posting>> = invert_index(&body_terms)
+            .into_iter()
+            .map(|(term, keys)| {
+                let documents = keys
+                    .into_iter()
+                    .map(|key| {
+                        (
+                            key,
+                            Posting {
+                                name_frequency: 0.0,
+                                body_frequency: 1.0,
+                            },
+                        )
+                    })
+                    .collect();
+                (term, documents)
+            })
+            .collect();
+        let mut document_lengths = HashMap::new();
+        for (key, monster) in monster_map.iter() {
+            let name_terms = monster
+                .name
+                .split_whitespace()
+                .map(|word| stem(&normalize(word)))
+                .filter(|term| !term.is_empty())
+                .collect::<Vec<_>>();
+            for term in &name_terms {
+                postings
+                    .entry(term.clone())
+                    .or_default()
+                    .entry(key.clone())
+                    .or_insert(Posting {
+                        name_frequency: 0.0,
+                        body_frequency: 0.0,
+                    })
+                    .name_frequency += 1.0;
+            }
+            document_lengths.insert(
+                key.clone(),
+                NAME_WEIGHT * name_terms.len() as f64 + body_terms[key].len() as f64,
+            );
+        }
+        let average_length =
+            document_lengths.values().sum::<f64>() / document_lengths.len().max(1) as f64;
+        let mut vocabulary = postings.keys().cloned().collect::<Vec<_>>();
+        vocabulary.sort();
+        let mut field_terms: HashMap<SearchField, HashMap<String, HashSet<String>>> =
+            HashMap::new();
+        let mut sequences: HashMap<String, Vec<(SearchField, Vec<String>)>> = HashMap::new();
+        for (key, monster) in monster_map.iter() {
+            for field in SearchField::ALL {
+                let texts = field.texts(monster);
+                field_terms.entry(field).or_default().insert(
+                    key.clone(),
+                    texts
+                        .iter()
+                        .flat_map(|text| text.tokenize())
+                        .map(|token| stem(&token))
+                        .collect(),
+                );
+                sequences
+                    .entry(key.clone())
+                    .or_default()
+                    .extend(texts.iter().map(|text| {
+                        (
+                            field,
+                            text.split_whitespace()
+                                .map(normalize)
+                                .filter(|word| !word.is_empty())
+                                .map(|word| stem(&word))
+                                .collect(),
+                        )
+                    }));
+            }
+        }
+        let field_indexes = field_terms
+            .iter()
+            .map(|(field, terms)| (*field, invert_index(terms)))
             .collect();
-        let key_index = Arc::new(invert_index(&tokens_map));
         MonsterSearch {
             monster_map,
-            key_index,
+            postings,
+            vocabulary,
+            document_lengths,
+            average_length,
+            field_indexes,
+            sequences,
         }
     }
 
-    pub fn search(&self, terms: &[&str]) -> Vec<Monster> {
-        let resolved_monster_names = terms
-            .iter()
-            .map(|term| term.to_lowercase())
-            .flat_map(|term| self.key_index.get(&term))
-            .map(|key_set| key_set.iter().collect::<HashSet<&String>>())
-            .reduce(|first, second| first.intersection(&second).cloned().collect())
-            .unwrap_or_else(HashSet::new);
-        resolved_monster_names
-            .iter()
-            .flat_map(|name| self.monster_map.get(*name))
-            .cloned()
-            .collect()
+    pub fn search(&self, query: &str) -> Result<Vec<ScoredMonster>, String> {
+        let Some(query) = parse_query(query)? else {
+            return Ok(vec![]);
+        };
+        let mut results = self
+            .evaluate(&query)
+            .into_iter()
+            .flat_map(|(key, score)| {
+                self.monster_map.get(&key).map(|monster| ScoredMonster {
+                    monster: monster.clone(),
+                    score,
+                })
+            })
+            .collect::<Vec<_>>();
+        results.sort_by(|first, second| {
+            second
+                .score
+                .total_cmp(&first.score)
+                .then_with(|| first.monster.name.cmp(&second.monster.name))
+        });
+        Ok(results)
+    }
+
+    fn evaluate(&self, query: &SearchQuery) -> HashMap<String, f64> {
+        match query {
+            SearchQuery::Term { field: None, word } => self.score_term(&self.expand(&stem(word))),
+            SearchQuery::Term {
+                field: Some(field),
+                word,
+            } => self.score_field_term(*field, &stem(word)),
+            SearchQuery::Phrase { field, words } => {
+                let stems = words.iter().map(|word| stem(word)).collect::<Vec<_>>();
+                let mut scores = self.evaluate(&SearchQuery::And(
+                    words
+                        .iter()
+                        .map(|word| SearchQuery::Term {
+                            field: *field,
+                            word: word.clone(),
+                        })
+                        .collect(),
+                ));
+                scores.retain(|key, _| self.contains_phrase(key, *field, &stems));
+                scores
+            }
+            SearchQuery::Not(query) => {
+                let excluded = self.evaluate(query);
+                self.monster_map
+                    .keys()
+                    .filter(|key| !excluded.contains_key(*key))
+                    .map(|key| (key.clone(), 0.0))
+                    .collect()
+            }
+            SearchQuery::And(clauses) => clauses
+                .iter()
+                .map(|clause| self.evaluate(clause))
+                .reduce(|first, second| {
+                    first
+                        .into_iter()
+                        .filter_map(|(key, score)| {
+                            second.get(&key).map(|other| (key, score + other))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            SearchQuery::Or(branches) => branches.iter().map(|branch| self.evaluate(branch)).fold(
+                HashMap::new(),
+                |mut scores, branch| {
+                    for (key, score) in branch {
+                        *scores.entry(key).or_insert(0.0) += score;
+                    }
+                    scores
+                },
+            ),
+        }
+    }
+
+    fn contains_phrase(&self, key: &str, field: Option<SearchField>, stems: &[String]) -> bool {
+        self.sequences.get(key).is_some_and(|sequences| {
+            sequences
+                .iter()
+                .filter(|(sequence_field, _)| field.is_none_or(|field| *sequence_field == field))
+                .any(|(_, words)| words.windows(stems.len()).any(|window| window == stems))
+        })
     }
+
+    fn score_field_term(&self, field: SearchField, term: &str) -> HashMap<String, f64> {
+        let mut scores = HashMap::new();
+        let Some(index) = self.field_indexes.get(&field) else {
+            return scores;
+        };
+        let documents = self.monster_map.len() as f64;
+        for (word, keys) in index {
+            let weight = match word.as_str() {
+                _ if word == term => 1.0,
+                _ if term.chars().count() >= MIN_PREFIX_LENGTH && word.starts_with(term) => {
+                    PREFIX_WEIGHT
+                }
+                _ => continue,
+            };
+            let score = weight * idf(documents, keys.len() as f64);
+            for key in keys {
+                let entry = scores.entry(key.clone()).or_insert(score);
+                *entry = entry.max(score);
+            }
+        }
+        scores
+    }
+
+    fn expand(&self, term: &str) -> HashMap<&str, f64> {
+        let mut expansions = HashMap::new();
+        if let Some((indexed, _)) = self.postings.get_key_value(term) {
+            expansions.insert(indexed.as_str(), 1.0);
+        }
+        if term.chars().count() >= MIN_PREFIX_LENGTH {
+            let start = self.vocabulary.partition_point(|word| word.as_str() < term);
+            for word in self.vocabulary[start..]
+                .iter()
+                .take_while(|word| word.starts_with(term))
+            {
+                expansions.entry(word.as_str()).or_insert(PREFIX_WEIGHT);
+            }
+        }
+        let length = term.chars().count();
+        let max_distance = match length {
+            _ if length >= DOUBLE_TYPO_LENGTH => 2,
+            _ if length >= MIN_TYPO_LENGTH => 1,
+            _ => 0,
+        };
+        if max_distance > 0 && expansions.is_empty() {
+            let correction = self
+                .vocabulary
+                .iter()
+                .filter(|word| word.chars().count().abs_diff(length) <= max_distance)
+                .map(|word| (word, edit_distance(term, word)))
+                .filter(|(_, distance)| (1..=max_distance).contains(distance))
+                .min_by(|(first, first_distance), (second, second_distance)| {
+                    first_distance
+                        .cmp(second_distance)
+                        .then_with(|| {
+                            self.postings[*second]
+                                .len()
+                                .cmp(&self.postings[*first].len())
+                        })
+                        .then_with(|| first.cmp(second))
+                });
+            if let Some((word, distance)) = correction {
+                expansions.insert(word.as_str(), TYPO_WEIGHT / distance as f64);
+            }
+        }
+        expansions
+    }
+
+    fn score_term(&self, expansions: &HashMap<&str, f64>) -> HashMap<String, f64> {
+        let documents = self.document_lengths.len() as f64;
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for (word, weight) in expansions {
+            let postings = &self.postings[*word];
+            let frequency = postings.len() as f64;
+            let idf = idf(documents, frequency);
+            for (key, posting) in postings {
+                let term_frequency = NAME_WEIGHT * posting.name_frequency + posting.body_frequency;
+                let length = self.document_lengths[key] / self.average_length;
+                let score = weight * idf * term_frequency * (BM25_K1 + 1.0)
+                    / (term_frequency + BM25_K1 * (1.0 - BM25_B + BM25_B * length));
+                let entry = scores.entry(key.clone()).or_insert(score);
+                *entry = entry.max(score);
+            }
+        }
+        scores
+    }
+}
+
+fn idf(documents: f64, frequency: f64) -> f64 {
+    (1.0 + (documents - frequency + 0.5) / (frequency + 0.5)).ln()
 }
 
 #[cfg(test)]
@@ -44,16 +313,130 @@ mod tests {
     use crate::utilities::index::vec_to_map;
     use crate::utilities::load_from_json::load_from_json;
 
-    #[test]
-    fn loads_from_json() {
+    fn monster_search() -> MonsterSearch {
         let monster_map = Arc::new(vec_to_map(
             &load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH),
             |monster| monster.name.clone(),
         ));
-        let monster_search = MonsterSearch::from_map(monster_map);
-        let evil_dragons = monster_search.search(&["eVil", "Dragon", "young"]);
+        MonsterSearch::from_map(monster_map)
+    }
+
+    fn names(results: &[ScoredMonster]) -> Vec<&str> {
+        results
+            .iter()
+            .map(|result| result.monster.name.as_str())
+            .collect()
+    }
 
-        assert!(evil_dragons.iter().all(|d| d.creature_type == "dragon"));
-        assert!(evil_dragons.iter().all(|d| d.alignment.contains("evil")));
+    #[test]
+    fn loads_from_json() {
+        let monster_search = monster_search();
+        let evil_dragons = monster_search.search("eVil Dragon young").unwrap();
+
+        assert!(!evil_dragons.is_empty());
+        assert!(
+            evil_dragons
+                .iter()
+                .all(|d| d.monster.creature_type == "dragon")
+        );
+        assert!(
+            evil_dragons
+                .iter()
+                .all(|d| d.monster.alignment.contains("evil"))
+        );
+    }
+
+    #[test]
+    fn test_results_are_ordered_by_score() {
+        let results = monster_search().search("dragon").unwrap();
+
+        assert!(results.len() > 1);
+        assert!(
+            results
+                .windows(2)
+                .all(|pair| pair[0].score >= pair[1].score)
+        );
+        assert!(results[0].monster.name.contains("Dragon"));
+    }
+
+    #[test]
+    fn test_name_matches_rank_above_trait_matches() {
+        let results = monster_search().search("goblin").unwrap();
+
+        assert_eq!(names(&results)[0], "Goblin");
+        assert!(
+            results
+                .iter()
+                .any(|result| !result.monster.name.contains("Goblin"))
+        );
+    }
+
+    #[test]
+    fn test_plural_prefix_and_typo() {
+        let monster_search = monster_search();
+
+        assert_eq!(
+            names(&monster_search.search("goblins").unwrap())[0],
+            "Goblin"
+        );
+        assert_eq!(names(&monster_search.search("abol").unwrap())[0], "Aboleth");
+        assert!(
+            names(&monster_search.search("dragn").unwrap())
+                .iter()
+                .any(|name| name.contains("Dragon"))
+        );
+    }
+
+    #[test]
+    fn test_unknown_query() {
+        let monster_search = monster_search();
+
+        assert!(monster_search.search("xq").unwrap().is_empty());
+        assert!(monster_search.search("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        let monster_search = monster_search();
+        let undead = monster_search.search("type:undead").unwrap();
+        let corporeal = monster_search
+            .search("type:undead NOT trait:incorporeal")
+            .unwrap();
+        let breath = monster_search
+            .search("type:dragon AND (damage:fire OR damage:cold)")
+            .unwrap();
+
+        assert!(corporeal.len() < undead.len());
+        assert!(names(&corporeal).contains(&"Zombie"));
+        assert!(!names(&corporeal).contains(&"Ghost"));
+        assert!(names(&breath).contains(&"Adult Red Dragon"));
+        assert!(names(&breath).contains(&"Adult White Dragon"));
+        assert!(!names(&breath).contains(&"Adult Black Dragon"));
+    }
+
+    #[test]
+    fn test_field_scoping_and_phrases() {
+        let monster_search = monster_search();
+        let amphibious = monster_search.search("trait:amphibious").unwrap();
+        let draconic = monster_search
+            .search("language:draconic -type:dragon")
+            .unwrap();
+        let pack_tactics = monster_search.search("\"pack tactics\"").unwrap();
+        let reversed = monster_search.search("\"tactics pack\"").unwrap();
+
+        assert!(names(&amphibious).contains(&"Aboleth"));
+        assert!(!draconic.is_empty());
+        assert!(
+            draconic
+                .iter()
+                .all(|result| result.monster.creature_type != "dragon")
+        );
+        assert!(names(&pack_tactics).contains(&"Wolf"));
+        assert!(reversed.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        assert!(monster_search().search("color:red").is_err());
     }
 }
@@ -0,0 +1,84 @@
+use crate::monsters::{Monster, SearchField, Tokenize};
+use std::str::FromStr;
+
+impl SearchField {
+    pub const ALL: [SearchField; 8] = [
+        SearchField::Name,
+        SearchField::Type,
+        SearchField::Size,
+        SearchField::Alignment,
+        SearchField::Language,
+        SearchField::Trait,
+        SearchField::Action,
+        SearchField::Damage,
+    ];
+
+    pub fn texts(&self, monster: &Monster) -> Vec<String> {
+        match self {
+            SearchField::Name => vec![monster.name.clone()],
+            SearchField::Type => vec![monster.creature_type.clone()],
+            SearchField::Size => vec![monster.size.to_string()],
+            SearchField::Alignment => vec![monster.alignment.clone()],
+            SearchField::Language => monster.languages.clone(),
+            SearchField::Trait => monster.traits.clone(),
+            SearchField::Action => monster
+                .actions
+                .iter()
+                .flat_map(|actions| actions.list.clone())
+                .chain(monster.legendary_actions.clone())
+                .chain(monster.reactions.clone())
+                .collect(),
+            SearchField::Damage => monster
+                .actions
+                .iter()
+                .flat_map(|actions| actions.attack_rolls.iter())
+                .flat_map(|attack_roll| attack_roll.damage.tokenize())
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for SearchField {
+    type Err = String;
+
+    fn from_str(field: &str) -> Result<Self, Self::Err> {
+        match field.to_lowercase().as_str() {
+            "name" => Ok(SearchField::Name),
+            "type" => Ok(SearchField::Type),
+            "size" => Ok(SearchField::Size),
+            "alignment" => Ok(SearchField::Alignment),
+            "language" => Ok(SearchField::Language),
+            "trait" => Ok(SearchField::Trait),
+            "action" => Ok(SearchField::Action),
+            "damage" => Ok(SearchField::Damage),
+            _ => Err(format!("Unknown search field `{field}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::load_from_json::load_from_json;
+
+    #[test]
+    fn test_parse_search_field() {
+        assert_eq!("Trait".parse::<SearchField>(), Ok(SearchField::Trait));
+        assert!("color".parse::<SearchField>().is_err());
+    }
+
+    #[test]
+    fn test_field_texts() {
+        let monsters = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH);
+        let aboleth = monsters.first().unwrap();
+
+        assert_eq!(SearchField::Name.texts(aboleth), vec!["Aboleth"]);
+        assert_eq!(SearchField::Type.texts(aboleth), vec!["aberration"]);
+        assert!(
+            SearchField::Damage
+                .texts(aboleth)
+                .contains(&"Bludgeoning".to_string())
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::monsters::{SearchField, SearchQuery, Tokenize};
+use crate::utilities::text::normalize;
+use std::iter::Peekable;
+use std::str::Chars;
+
+const MAX_QUERY_LENGTH: usize = 1_000;
+const MAX_DEPTH: usize = 32;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Term {
+        field: Option<SearchField>,
+        text: String,
+        quoted: bool,
+    },
+}
+
+pub fn parse_query(query: &str) -> Result<Option<SearchQuery>, String> {
+    if query.chars().count() > MAX_QUERY_LENGTH {
+        return Err(format!(
+            "Query is longer than {MAX_QUERY_LENGTH} characters"
+        ));
+    }
+    let tokens = lex(query)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        depth: 0,
+    };
+    let parsed = parser.parse_or()?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(parsed),
+        Some(Token::Close) => Err("Unexpected `)` in query".to_string()),
+        Some(_) => Err("Unexpected operator in query".to_string()),
+    }
+}
+
+fn lex(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = query.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            _ if ch.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' => {
+                chars.next();
+                tokens.push(Token::Term {
+                    field: None,
+                    text: read_phrase(&mut chars)?,
+                    quoted: true,
+                });
+            }
+            '-' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            _ => {
+                let word = read_word(&mut chars);
+                tokens.push(match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => match word.split_once(':') {
+                        Some((field, text)) => {
+                            let field = Some(field.parse::<SearchField>()?);
+                            match text.is_empty() && chars.peek() == Some(&'"') {
+                                true => {
+                                    chars.next();
+                                    Token::Term {
+                                        field,
+                                        text: read_phrase(&mut chars)?,
+                                        quoted: true,
+                                    }
+                                }
+                                false => Token::Term {
+                                    field,
+                                    text: text.to_string(),
+                                    quoted: false,
+                                },
+                            }
+                        }
+                        None => Token::Term {
+                            field: None,
+                            text: word,
+                            quoted: false,
+                        },
+                    },
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn read_word(chars: &mut Peekable<Chars>) -> String {
+    let mut word = String::new();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() || matches!(ch, '(' | ')' | '"') {
+            break;
+        }
+        word.push(ch);
+        chars.next();
+    }
+    word
+}
+
+fn read_phrase(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    let mut phrase = String::new();
+    for ch in chars.by_ref() {
+        if ch == '"' {
+            return Ok(phrase);
+        }
+        phrase.push(ch);
+    }
+    Err("Unterminated `\"` in query".to_string())
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn descend(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        match self.depth > MAX_DEPTH {
+            true => Err(format!("Query is nested deeper than {MAX_DEPTH}")),
+            false => Ok(()),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Option<SearchQuery>, String> {
+        let mut branches = vec![];
+        let mut alternatives = false;
+        loop {
+            let start = self.position;
+            branches.extend(self.parse_and()?);
+            let next_is_or = self.tokens.get(self.position) == Some(&Token::Or);
+            if self.position == start && (alternatives || next_is_or) {
+                return Err("Expected a search term around `OR`".to_string());
+            }
+            if !next_is_or {
+                break;
+            }
+            alternatives = true;
+            self.position += 1;
+        }
+        Ok(match branches.len() {
+            0 => None,
+            1 => branches.pop(),
+            _ => Some(SearchQuery::Or(branches)),
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Option<SearchQuery>, String> {
+        let mut clauses = vec![];
+        loop {
+            match self.tokens.get(self.position) {
+                None | Some(Token::Or) | Some(Token::Close) => break,
+                Some(Token::And) => self.position += 1,
+                Some(_) => clauses.extend(self.parse_unary()?),
+            }
+        }
+        Ok(match clauses.len() {
+            0 => None,
+            1 => clauses.pop(),
+            _ => Some(SearchQuery::And(clauses)),
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Option<SearchQuery>, String> {
+        let Some(token) = self.tokens.get(self.position) else {
+            return Err("Expected a search term at the end of the query".to_string());
+        };
+        self.position += 1;
+        match token {
+            Token::Not => {
+                self.descend()?;
+                let operand = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(operand.map(|query| SearchQuery::Not(Box::new(query))))
+            }
+            Token::Open => {
+                self.descend()?;
+                let group = self.parse_or()?;
+                self.depth -= 1;
+                match self.tokens.get(self.position) {
+                    Some(Token::Close) => {
+                        self.position += 1;
+                        Ok(group)
+                    }
+                    _ => Err("Missing `)` in query".to_string()),
+                }
+            }
+            Token::Term {
+                field,
+                text,
+                quoted,
+            } => Ok(term(*field, text, *quoted)),
+            _ => Err("Expected a search term".to_string()),
+        }
+    }
+}
+
+fn term(field: Option<SearchField>, text: &str, quoted: bool) -> Option<SearchQuery> {
+    let mut words = match quoted {
+        true => text
+            .split_whitespace()
+            .map(normalize)
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>(),
+        false => text.tokenize().into_iter().collect(),
+    };
+    match words.len() {
+        0 => None,
+        1 => words.pop().map(|word| SearchQuery::Term { field, word }),
+        _ => Some(SearchQuery::Phrase { field, words }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(field: Option<SearchField>, word: &str) -> SearchQuery {
+        SearchQuery::Term {
+            field,
+            word: word.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_implicit_and() {
+        assert_eq!(
+            parse_query("young  evil dragon"),
+            Ok(Some(SearchQuery::And(vec![
+                word(None, "young"),
+                word(None, "evil"),
+                word(None, "dragon"),
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_parse_operators_and_fields() {
+        assert_eq!(
+            parse_query("type:undead NOT trait:incorporeal AND (fire OR -cold)"),
+            Ok(Some(SearchQuery::And(vec![
+                word(Some(SearchField::Type), "undead"),
+                SearchQuery::Not(Box::new(word(Some(SearchField::Trait), "incorporeal"))),
+                SearchQuery::Or(vec![
+                    word(None, "fire"),
+                    SearchQuery::Not(Box::new(word(None, "cold"))),
+                ]),
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_parse_phrases() {
+        assert_eq!(
+            parse_query("\"Pack Tactics\" OR action:\"Fire Breath\""),
+            Ok(Some(SearchQuery::Or(vec![
+                SearchQuery::Phrase {
+                    field: None,
+                    words: vec!["pack".to_string(), "tactics".to_string()],
+                },
+                SearchQuery::Phrase {
+                    field: Some(SearchField::Action),
+                    words: vec!["fire".to_string(), "breath".to_string()],
+                },
+            ])))
+        );
+        assert_eq!(parse_query("\"Orc\""), Ok(Some(word(None, "orc"))));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(parse_query(""), Ok(None));
+        assert_eq!(parse_query("  & "), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for query in [
+            "(dragon",
+            "dragon)",
+            "\"pack tactics",
+            "color:red",
+            "dragon NOT",
+            "OR dragon",
+            "dragon OR",
+        ] {
+            assert!(parse_query(query).is_err(), "{query}");
+        }
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert!(parse_query(&format!("{}dragon", "-".repeat(8_000))).is_err());
+        assert!(parse_query(&"(".repeat(3_000)).is_err());
+        assert!(parse_query(&format!("{}dragon{}", "(".repeat(40), ")".repeat(40))).is_err());
+        assert!(parse_query(&format!("{}dragon{}", "(".repeat(30), ")".repeat(30))).is_ok());
+        assert!(parse_query(&"dragon ".repeat(200)).is_err());
+    }
+}
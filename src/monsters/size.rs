@@ -0,0 +1,29 @@
+use crate::monsters::Size;
+use std::str::FromStr;
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(size: &str) -> Result<Self, Self::Err> {
+        match size.to_lowercase().as_str() {
+            "tiny" => Ok(Size::Tiny),
+            "small" => Ok(Size::Small),
+            "medium" => Ok(Size::Medium),
+            "large" => Ok(Size::Large),
+            "huge" => Ok(Size::Huge),
+            "gargantuan" => Ok(Size::Gargantuan),
+            _ => Err(format!("Unknown size `{size}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!("Huge".parse::<Size>(), Ok(Size::Huge));
+        assert!("colossal".parse::<Size>().is_err());
+    }
+}
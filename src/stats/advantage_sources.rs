@@ -0,0 +1,57 @@
+use crate::stats::{AdvantageSources, AdvantageType};
+
+impl AdvantageSources {
+    pub fn resolve(&self) -> Option<AdvantageType> {
+        match (self.advantage.is_empty(), self.disadvantage.is_empty()) {
+            (false, true) if self.elven_accuracy => Some(AdvantageType::ElvenAccuracy),
+            (false, true) => Some(AdvantageType::Advantage),
+            (true, false) => Some(AdvantageType::Disadvantage),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(
+        advantage: &[&str],
+        disadvantage: &[&str],
+        elven_accuracy: bool,
+    ) -> AdvantageSources {
+        AdvantageSources {
+            advantage: advantage.iter().map(|source| source.to_string()).collect(),
+            disadvantage: disadvantage
+                .iter()
+                .map(|source| source.to_string())
+                .collect(),
+            elven_accuracy,
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(sources(&[], &[], false).resolve(), None);
+        assert_eq!(
+            sources(&["flanking", "reckless"], &[], false).resolve(),
+            Some(AdvantageType::Advantage)
+        );
+        assert_eq!(
+            sources(&[], &["prone"], true).resolve(),
+            Some(AdvantageType::Disadvantage)
+        );
+        assert_eq!(
+            sources(&["reckless"], &[], true).resolve(),
+            Some(AdvantageType::ElvenAccuracy)
+        );
+    }
+
+    #[test]
+    fn test_resolve_cancels() {
+        assert_eq!(
+            sources(&["flanking", "reckless", "hidden"], &["prone"], true).resolve(),
+            None
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::stats::{AttackOutcome, StatRoll};
+
+impl AttackOutcome {
+    pub fn resolve(to_hit: &StatRoll, target_ac: i32) -> Self {
+        match to_hit {
+            StatRoll {
+                is_critical_success: true,
+                ..
+            } => AttackOutcome::CriticalHit,
+            StatRoll {
+                is_critical_failure: true,
+                ..
+            } => AttackOutcome::CriticalMiss,
+            StatRoll { result, .. } if *result >= target_ac => AttackOutcome::Hit,
+            _ => AttackOutcome::Miss,
+        }
+    }
+
+    pub fn is_hit(&self) -> bool {
+        matches!(self, AttackOutcome::Hit | AttackOutcome::CriticalHit)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        matches!(self, AttackOutcome::CriticalHit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::{Die, Roll};
+    use crate::stats::KeptRoll;
+
+    fn to_hit(natural: i32, total: i32, critical_threshold: i32) -> StatRoll {
+        StatRoll {
+            first_roll: vec![Roll::new(Die::D20, natural)],
+            second_roll: None,
+            third_roll: None,
+            bonus_roll: None,
+            result: total,
+            kept: KeptRoll::First,
+            natural,
+            is_critical_success: natural >= critical_threshold,
+            is_critical_failure: natural == 1,
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            AttackOutcome::resolve(&to_hit(20, 21, 20), 30),
+            AttackOutcome::CriticalHit
+        );
+        assert_eq!(
+            AttackOutcome::resolve(&to_hit(1, 15, 20), 10),
+            AttackOutcome::CriticalMiss
+        );
+        assert_eq!(
+            AttackOutcome::resolve(&to_hit(10, 15, 20), 15),
+            AttackOutcome::Hit
+        );
+        assert_eq!(
+            AttackOutcome::resolve(&to_hit(10, 14, 20), 15),
+            AttackOutcome::Miss
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_crit_range() {
+        assert_eq!(
+            AttackOutcome::resolve(&to_hit(19, 20, 19), 30),
+            AttackOutcome::CriticalHit
+        );
+        assert_eq!(
+            AttackOutcome::resolve(&to_hit(19, 20, 20), 30),
+            AttackOutcome::Miss
+        );
+    }
+
+    #[test]
+    fn test_hits() {
+        assert!(AttackOutcome::CriticalHit.is_hit());
+        assert!(AttackOutcome::CriticalHit.is_critical());
+        assert!(AttackOutcome::Hit.is_hit());
+        assert!(!AttackOutcome::Hit.is_critical());
+        assert!(!AttackOutcome::Miss.is_hit());
+        assert!(!AttackOutcome::CriticalMiss.is_hit());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::stats::DifficultyCheck;
+
+impl DifficultyCheck {
+    pub fn against(result: i32, dc: i32) -> Self {
+        Self {
+            dc,
+            passed: result >= dc,
+            margin: result - dc,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against() {
+        assert_eq!(
+            DifficultyCheck::against(15, 15),
+            DifficultyCheck {
+                dc: 15,
+                passed: true,
+                margin: 0,
+            }
+        );
+        assert_eq!(
+            DifficultyCheck::against(9, 13),
+            DifficultyCheck {
+                dc: 13,
+                passed: false,
+                margin: -4,
+            }
+        );
+    }
+}
@@ -1,10 +1,16 @@
+pub mod advantage_sources;
+pub mod attack_outcome;
+pub mod difficulty_check;
 pub mod modifier_extractor;
 pub mod stat_roller;
 
-use crate::dice::Roll;
-use serde::Deserialize;
+use crate::dice::{Expression, Roll};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-#[derive(Deserialize, Clone)]
+pub const DEFAULT_CRITICAL_THRESHOLD: i32 = 20;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub enum StatType {
     #[serde(rename = "str")]
     Strength,
@@ -60,26 +66,73 @@ pub enum SkillType {
     Survival,
 }
 
-#[derive(Deserialize, Hash, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Hash, Eq, PartialEq, Clone, Copy)]
 pub enum AdvantageType {
     #[serde(rename = "advantage")]
     Advantage,
     #[serde(rename = "disadvantage")]
     Disadvantage,
+    #[serde(rename = "elvenAccuracy")]
+    ElvenAccuracy,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone, Default)]
+pub struct AdvantageSources {
+    pub advantage: Vec<String>,
+    pub disadvantage: Vec<String>,
+    #[serde(rename = "elvenAccuracy")]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub elven_accuracy: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum AttackOutcome {
+    #[serde(rename = "hit")]
+    Hit,
+    #[serde(rename = "miss")]
+    Miss,
+    #[serde(rename = "criticalHit")]
+    CriticalHit,
+    #[serde(rename = "criticalMiss")]
+    CriticalMiss,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum KeptRoll {
+    #[serde(rename = "first")]
+    First,
+    #[serde(rename = "second")]
+    Second,
+    #[serde(rename = "third")]
+    Third,
 }
 
-#[derive(Deserialize, Hash, Eq, PartialEq)]
-pub enum Critical {
-    #[serde(rename = "critical")]
-    Critical,
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub struct DifficultyCheck {
+    pub dc: i32,
+    pub passed: bool,
+    pub margin: i32,
 }
 
 pub struct StatRoll {
     pub first_roll: Vec<Roll>,
     pub second_roll: Option<Vec<Roll>>,
+    pub third_roll: Option<Vec<Roll>>,
+    pub bonus_roll: Option<Vec<Roll>>,
     pub result: i32,
+    pub kept: KeptRoll,
+    pub natural: i32,
+    pub is_critical_success: bool,
+    pub is_critical_failure: bool,
 }
 
 pub trait StatRoller {
-    fn roll_stat(&self, modifier: i32, advantage_status: &Option<&AdvantageType>) -> StatRoll;
+    fn roll_stat(
+        &self,
+        modifier: i32,
+        advantage_status: &Option<&AdvantageType>,
+        critical_threshold: i32,
+        bonus: Option<&Expression>,
+    ) -> StatRoll;
+    fn seeded(&self, seed: u64) -> Arc<dyn StatRoller + Send + Sync>;
 }
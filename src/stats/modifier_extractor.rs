@@ -74,7 +74,9 @@ pub fn build_attack_modifier_extractor() -> ModifierExtractor<usize> {
 mod tests {
     use super::*;
     use crate::monsters;
-    use crate::monsters::{AttackRoll, AttackType, Challenge, Monster, Size, Skills, Speed, Stats};
+    use crate::monsters::{
+        AttackRoll, AttackType, Challenge, DamageModifiers, Monster, Size, Skills, Speed, Stats,
+    };
     use crate::stats::{SkillType, StatType};
 
     // Helper function to create a Monster instance
@@ -137,6 +139,7 @@ mod tests {
             }),
             legendary_actions: vec![],
             reactions: vec![],
+            damage_modifiers: DamageModifiers::default(),
             challenge: Challenge {
                 rating: "".to_string(),
                 xp: 0,
@@ -1,6 +1,5 @@
-use crate::dice::{DiceRoller, Die};
-use crate::stats::{AdvantageType, StatRoll, StatRoller};
-use std::cmp::{max, min};
+use crate::dice::{DiceRoller, Die, Expression, Roll};
+use crate::stats::{AdvantageType, KeptRoll, StatRoll, StatRoller};
 use std::sync::Arc;
 
 pub struct StatRollerImpl {
@@ -14,44 +13,87 @@ impl StatRollerImpl {
 }
 
 impl StatRoller for StatRollerImpl {
-    fn roll_stat(&self, modifier: i32, advantage_status: &Option<&AdvantageType>) -> StatRoll {
+    fn roll_stat(
+        &self,
+        modifier: i32,
+        advantage_status: &Option<&AdvantageType>,
+        critical_threshold: i32,
+        bonus: Option<&Expression>,
+    ) -> StatRoll {
         let die_roll_expression = [(Die::D20, 1), (Die::Raw, modifier)];
-        let (first_roll, first_roll_total) = self.dice_roller.roll(&die_roll_expression);
-        let (second_roll, result) = match advantage_status {
-            None => (None, first_roll_total),
-            Some(advantage_type) => {
-                let (second_roll, second_roll_total) = self.dice_roller.roll(&die_roll_expression);
-                match advantage_type {
-                    AdvantageType::Advantage => {
-                        (Some(second_roll), max(first_roll_total, second_roll_total))
-                    }
-                    AdvantageType::Disadvantage => {
-                        (Some(second_roll), min(first_roll_total, second_roll_total))
-                    }
-                }
-            }
+        let count = match advantage_status {
+            None => 1,
+            Some(AdvantageType::ElvenAccuracy) => 3,
+            Some(_) => 2,
         };
+        let rolls = (0..count)
+            .map(|_| self.dice_roller.roll(&die_roll_expression))
+            .collect::<Vec<_>>();
+        let kept_index =
+            rolls
+                .iter()
+                .enumerate()
+                .skip(1)
+                .fold(0, |kept_index, (index, (_, total))| {
+                    let kept_total = rolls[kept_index].1;
+                    let better = match advantage_status {
+                        Some(AdvantageType::Disadvantage) => *total < kept_total,
+                        _ => *total > kept_total,
+                    };
+                    match better {
+                        true => index,
+                        false => kept_index,
+                    }
+                });
+        let bonus_roll = bonus.map(|bonus| self.dice_roller.roll_expression(bonus));
+        let result = rolls[kept_index].1 + bonus_roll.as_ref().map_or(0, |bonus| bonus.total);
+        let natural = natural_face(&rolls[kept_index].0);
+        let mut rolls = rolls.into_iter().map(|(rolls, _)| rolls);
         StatRoll {
-            first_roll,
-            second_roll,
+            first_roll: rolls.next().unwrap_or_default(),
+            second_roll: rolls.next(),
+            third_roll: rolls.next(),
+            bonus_roll: bonus_roll.map(|bonus| bonus.rolls),
             result,
+            kept: match kept_index {
+                0 => KeptRoll::First,
+                1 => KeptRoll::Second,
+                _ => KeptRoll::Third,
+            },
+            natural,
+            is_critical_success: natural >= critical_threshold,
+            is_critical_failure: natural == 1,
         }
     }
+
+    fn seeded(&self, seed: u64) -> Arc<dyn StatRoller + Send + Sync> {
+        Arc::new(StatRollerImpl::new(self.dice_roller.seeded(seed)))
+    }
+}
+
+fn natural_face(rolls: &[Roll]) -> i32 {
+    rolls
+        .iter()
+        .find(|roll| roll.die == Die::D20)
+        .map_or(0, |roll| roll.value)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dice::Roll;
+    use crate::dice::DiceExpressionParser;
+    use crate::dice::dice_expression_parser::DiceExpressionParserImpl;
     use crate::dice::dice_roller::DiceRollerImpl;
     use crate::dice::die_roller::DieRollerImpl;
+    use crate::dice::sequence_die_roller::SequenceDieRoller;
+    use std::cmp::{max, min};
     use std::sync::Arc;
 
     #[test]
     fn test_roll_stat_no_advantage() {
         let dice_roller = Arc::new(DiceRollerImpl::new(Arc::new(DieRollerImpl::default())));
         let stat_roller = StatRollerImpl::new(dice_roller);
-        let result = stat_roller.roll_stat(0, &None);
+        let result = stat_roller.roll_stat(0, &None, 20, None);
         assert!(result.result <= 20 && result.result >= 0);
         assert_eq!(result.first_roll.first().unwrap().value, result.result);
     }
@@ -60,7 +102,7 @@ mod tests {
     fn test_roll_stat_with_advantage() {
         let dice_roller = Arc::new(DiceRollerImpl::new(Arc::new(DieRollerImpl::default())));
         let stat_roller = StatRollerImpl::new(dice_roller);
-        let result = stat_roller.roll_stat(3, &Some(&AdvantageType::Advantage));
+        let result = stat_roller.roll_stat(3, &Some(&AdvantageType::Advantage), 20, None);
         assert_eq!(
             result.result,
             max(
@@ -74,7 +116,7 @@ mod tests {
     fn test_roll_stat_with_disadvantage() {
         let dice_roller = Arc::new(DiceRollerImpl::new(Arc::new(DieRollerImpl::default())));
         let stat_roller = StatRollerImpl::new(dice_roller);
-        let result = stat_roller.roll_stat(3, &Some(&AdvantageType::Disadvantage));
+        let result = stat_roller.roll_stat(3, &Some(&AdvantageType::Disadvantage), 20, None);
         assert_eq!(
             result.result,
             min(
@@ -84,6 +126,106 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seeded_roll_stat_repeats() {
+        let dice_roller = Arc::new(DiceRollerImpl::new(Arc::new(DieRollerImpl::default())));
+        let stat_roller = StatRollerImpl::new(dice_roller);
+        let first = stat_roller
+            .seeded(99)
+            .roll_stat(2, &Some(&AdvantageType::Advantage), 20, None);
+        let second =
+            stat_roller
+                .seeded(99)
+                .roll_stat(2, &Some(&AdvantageType::Advantage), 20, None);
+
+        assert_eq!(first.first_roll, second.first_roll);
+        assert_eq!(first.second_roll, second.second_roll);
+        assert_eq!(first.result, second.result);
+    }
+
+    fn stat_roller_with(values: Vec<i32>) -> StatRollerImpl {
+        StatRollerImpl::new(Arc::new(DiceRollerImpl::new(Arc::new(
+            SequenceDieRoller::new(values),
+        ))))
+    }
+
+    #[test]
+    fn test_roll_stat_reports_kept_natural() {
+        let result =
+            stat_roller_with(vec![4, 17]).roll_stat(2, &Some(&AdvantageType::Advantage), 20, None);
+
+        assert_eq!(result.kept, KeptRoll::Second);
+        assert_eq!(result.natural, 17);
+        assert_eq!(result.result, 19);
+        assert!(!result.is_critical_success);
+        assert!(!result.is_critical_failure);
+    }
+
+    #[test]
+    fn test_roll_stat_critical_failure_with_disadvantage() {
+        let result = stat_roller_with(vec![20, 1]).roll_stat(
+            5,
+            &Some(&AdvantageType::Disadvantage),
+            20,
+            None,
+        );
+
+        assert_eq!(result.kept, KeptRoll::Second);
+        assert_eq!(result.natural, 1);
+        assert!(result.is_critical_failure);
+        assert!(!result.is_critical_success);
+    }
+
+    #[test]
+    fn test_roll_stat_with_elven_accuracy() {
+        let result = stat_roller_with(vec![7, 18, 18]).roll_stat(
+            1,
+            &Some(&AdvantageType::ElvenAccuracy),
+            20,
+            None,
+        );
+
+        assert!(result.third_roll.is_some());
+        assert_eq!(result.kept, KeptRoll::Second);
+        assert_eq!(result.natural, 18);
+        assert_eq!(result.result, 19);
+    }
+
+    #[test]
+    fn test_roll_stat_with_bonus_dice() {
+        let bless = DiceExpressionParserImpl::default()
+            .parse_expression("1d4")
+            .unwrap();
+        let bane = DiceExpressionParserImpl::default()
+            .parse_expression("-1d4")
+            .unwrap();
+        let stat_roller = stat_roller_with(vec![12, 3, 12, 3]);
+
+        let blessed = stat_roller.roll_stat(2, &None, 20, Some(&bless));
+        let baned = stat_roller.roll_stat(2, &None, 20, Some(&bane));
+
+        assert_eq!(blessed.result, 17);
+        assert_eq!(blessed.natural, 12);
+        assert_eq!(blessed.bonus_roll.unwrap()[0].value, 3);
+        assert_eq!(baned.result, 11);
+    }
+
+    #[test]
+    fn test_roll_stat_critical_range() {
+        let stat_roller = stat_roller_with(vec![19, 19]);
+
+        assert!(
+            stat_roller
+                .roll_stat(0, &None, 19, None)
+                .is_critical_success
+        );
+        assert!(
+            !stat_roller
+                .roll_stat(0, &None, 20, None)
+                .is_critical_success
+        );
+    }
+
     fn total_roll(rolls: &[Roll]) -> i32 {
         rolls
             .iter()
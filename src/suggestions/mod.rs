@@ -0,0 +1,15 @@
+pub mod monster_suggester;
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Suggestion {
+    pub name: String,
+    pub challenge: String,
+    #[serde(rename = "creatureType")]
+    pub creature_type: String,
+}
+
+pub trait MonsterSuggester {
+    fn suggest(&self, prefix: &str, limit: usize) -> Vec<Suggestion>;
+}
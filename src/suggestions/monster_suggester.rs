@@ -0,0 +1,131 @@
+use crate::monsters::Monster;
+use crate::suggestions::{MonsterSuggester, Suggestion};
+use std::collections::HashSet;
+
+struct IndexEntry {
+    key: String,
+    word_position: usize,
+    suggestion: usize,
+}
+
+pub struct MonsterSuggesterImpl {
+    suggestions: Vec<Suggestion>,
+    index: Vec<IndexEntry>,
+}
+
+impl MonsterSuggesterImpl {
+    pub fn new(monsters: &[Monster]) -> Self {
+        let suggestions = monsters
+            .iter()
+            .map(|monster| Suggestion {
+                name: monster.name.clone(),
+                challenge: monster.challenge.rating.clone(),
+                creature_type: monster.creature_type.clone(),
+            })
+            .collect::<Vec<_>>();
+        let mut index = suggestions
+            .iter()
+            .enumerate()
+            .flat_map(|(suggestion, Suggestion { name, .. })| {
+                let words = collapse_whitespace(name)
+                    .split(' ')
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                (0..words.len())
+                    .map(|word_position| IndexEntry {
+                        key: words[word_position..].join(" "),
+                        word_position,
+                        suggestion,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        index.sort_by(|first, second| first.key.cmp(&second.key));
+        MonsterSuggesterImpl { suggestions, index }
+    }
+}
+
+impl MonsterSuggester for MonsterSuggesterImpl {
+    fn suggest(&self, prefix: &str, limit: usize) -> Vec<Suggestion> {
+        let prefix = collapse_whitespace(prefix);
+        if prefix.is_empty() {
+            return vec![];
+        }
+        let start = self
+            .index
+            .partition_point(|entry| entry.key.as_str() < prefix.as_str());
+        let mut matches = self.index[start..]
+            .iter()
+            .take_while(|entry| entry.key.starts_with(&prefix))
+            .map(|entry| (entry.word_position > 0, &self.suggestions[entry.suggestion]))
+            .collect::<Vec<_>>();
+        matches.sort_by(|(first_inner, first), (second_inner, second)| {
+            first_inner
+                .cmp(second_inner)
+                .then_with(|| first.name.len().cmp(&second.name.len()))
+                .then_with(|| first.name.cmp(&second.name))
+        });
+        let mut seen = HashSet::new();
+        matches
+            .into_iter()
+            .filter(|(_, suggestion)| seen.insert(suggestion.name.as_str()))
+            .take(limit)
+            .map(|(_, suggestion)| suggestion.clone())
+            .collect()
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::load_from_json::load_from_json;
+
+    fn suggester() -> MonsterSuggesterImpl {
+        MonsterSuggesterImpl::new(&load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH))
+    }
+
+    fn names(suggestions: &[Suggestion]) -> Vec<&str> {
+        suggestions
+            .iter()
+            .map(|suggestion| suggestion.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_suggest_name_prefix_first() {
+        let suggestions = suggester().suggest("GOB", 10);
+
+        assert_eq!(names(&suggestions), vec!["Goblin"]);
+        assert_eq!(suggestions[0].challenge, "1/4");
+        assert_eq!(suggestions[0].creature_type, "humanoid");
+    }
+
+    #[test]
+    fn test_suggest_inner_words_and_limit() {
+        let suggestions = suggester().suggest("red  dr", 3);
+
+        assert_eq!(
+            names(&suggestions),
+            vec![
+                "Red Dragon Wyrmling",
+                "Adult Red Dragon",
+                "Young Red Dragon"
+            ]
+        );
+        assert!(suggester().suggest("dragon", 50).len() > 3);
+    }
+
+    #[test]
+    fn test_suggest_empty() {
+        assert!(suggester().suggest(" ", 10).is_empty());
+        assert!(suggester().suggest("zzz", 10).is_empty());
+    }
+}
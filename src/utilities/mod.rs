@@ -1,4 +1,9 @@
 pub mod index;
 pub mod load_from_json;
+pub mod save_to_json;
+pub mod text;
 
 pub const MONSTERS_JSON_PATH: &str = "user_data/monsters.json";
+pub const MACROS_JSON_PATH: &str = "user_data/macros.json";
+pub const HISTORY_LOG_VARIABLE: &str = "DM_TOOL_HISTORY_LOG";
+pub const CRITICAL_RULE_VARIABLE: &str = "DM_TOOL_CRITICAL_RULE";
@@ -0,0 +1,39 @@
+use serde::Serialize;
+use std::fs::{File, create_dir_all, rename};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub fn save_to_json<T>(path: &str, value: &T) -> Result<(), String>
+where
+    T: Serialize,
+{
+    if let Some(parent) = Path::new(path).parent() {
+        create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let staging_path = format!("{path}.tmp");
+    let file = File::create(&staging_path).map_err(|error| error.to_string())?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, value).map_err(|error| error.to_string())?;
+    writer.flush().map_err(|error| error.to_string())?;
+    rename(&staging_path, path).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::load_from_json::load_from_json;
+    use std::env::temp_dir;
+
+    #[test]
+    fn saves_to_json() {
+        let path = temp_dir()
+            .join("dm-tool-save-to-json")
+            .join("values.json")
+            .to_string_lossy()
+            .to_string();
+
+        save_to_json(&path, &vec![1, 2, 3]).unwrap();
+
+        assert_eq!(load_from_json::<Vec<i32>>(&path), vec![1, 2, 3]);
+    }
+}
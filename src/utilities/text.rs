@@ -0,0 +1,90 @@
+pub fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+pub fn stem(word: &str) -> String {
+    let length = word.chars().count();
+    let strip = |suffix: &str, replacement: &str| {
+        format!("{}{replacement}", &word[..word.len() - suffix.len()])
+    };
+    match word {
+        _ if length <= 3 || !word.is_ascii() => word.to_string(),
+        _ if word.ends_with("sses") => strip("es", ""),
+        _ if word.ends_with("ies") && length > 4 => strip("ies", "y"),
+        _ if word.ends_with("ves") && length > 4 => strip("ves", "f"),
+        _ if ["ches", "shes", "xes", "zes"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix)) =>
+        {
+            strip("es", "")
+        }
+        _ if word.ends_with("ing") && length > 5 => strip("ing", ""),
+        _ if word.ends_with("ed") && length > 4 && !word.ends_with("eed") => strip("ed", ""),
+        _ if word.ends_with('s')
+            && !["ss", "us", "is"]
+                .iter()
+                .any(|suffix| word.ends_with(suffix)) =>
+        {
+            strip("s", "")
+        }
+        _ => word.to_string(),
+    }
+}
+
+pub fn edit_distance(first: &str, second: &str) -> usize {
+    let second = second.chars().collect::<Vec<_>>();
+    let mut previous = (0..=second.len()).collect::<Vec<_>>();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut current = vec![i + 1; second.len() + 1];
+        for (j, second_char) in second.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(first_char != *second_char))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[second.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Dragon's,"), "dragons");
+    }
+
+    #[test]
+    fn test_stem() {
+        for (word, expected) in [
+            ("goblins", "goblin"),
+            ("harpies", "harpy"),
+            ("wolves", "wolf"),
+            ("witches", "witch"),
+            ("boxes", "box"),
+            ("bites", "bite"),
+            ("flying", "fly"),
+            ("charmed", "charm"),
+            ("hydras", "hydra"),
+            ("abyss", "abyss"),
+            ("incubus", "incubus"),
+            ("kiss", "kiss"),
+            ("orc", "orc"),
+        ] {
+            assert_eq!(stem(word), expected, "{word}");
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("dragon", "dragon"), 0);
+        assert_eq!(edit_distance("dragn", "dragon"), 1);
+        assert_eq!(edit_distance("beholdr", "beholder"), 1);
+        assert_eq!(edit_distance("kraken", "karken"), 2);
+        assert_eq!(edit_distance("", "ogre"), 4);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::validation::AnomalyKind;
+use std::str::FromStr;
+
+impl FromStr for AnomalyKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_lowercase().as_str() {
+            "missing" => Ok(AnomalyKind::Missing),
+            "inconsistent" => Ok(AnomalyKind::Inconsistent),
+            _ => Err(format!("Unknown anomaly kind `{kind}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_anomaly_kind() {
+        assert_eq!("Missing".parse::<AnomalyKind>(), Ok(AnomalyKind::Missing));
+        assert!("wrong".parse::<AnomalyKind>().is_err());
+    }
+}
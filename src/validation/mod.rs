@@ -0,0 +1,26 @@
+pub mod anomaly_kind;
+pub mod monster_validator;
+
+use crate::monsters::Monster;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum AnomalyKind {
+    #[serde(rename = "missing")]
+    Missing,
+    #[serde(rename = "inconsistent")]
+    Inconsistent,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+pub struct Anomaly {
+    pub monster: String,
+    pub field: String,
+    pub kind: AnomalyKind,
+    pub value: i32,
+    pub expected: Vec<i32>,
+}
+
+pub trait MonsterValidator {
+    fn validate(&self, monster: &Monster) -> Vec<Anomaly>;
+}
@@ -0,0 +1,218 @@
+use crate::derived::DerivedStatsCalculator;
+use crate::monsters::{Monster, Stats};
+use crate::validation::{Anomaly, AnomalyKind, MonsterValidator};
+use std::sync::Arc;
+
+pub struct MonsterValidatorImpl {
+    derived_stats_calculator: Arc<dyn DerivedStatsCalculator + Send + Sync>,
+}
+
+impl MonsterValidatorImpl {
+    pub fn new(derived_stats_calculator: Arc<dyn DerivedStatsCalculator + Send + Sync>) -> Self {
+        Self {
+            derived_stats_calculator,
+        }
+    }
+}
+
+impl MonsterValidator for MonsterValidatorImpl {
+    fn validate(&self, monster: &Monster) -> Vec<Anomaly> {
+        let proficiency_bonus = self
+            .derived_stats_calculator
+            .derive(monster)
+            .proficiency_bonus;
+        let modifiers = &monster.modifiers;
+        let lists_untrained_saves = stats(&monster.saving_throws)
+            .into_iter()
+            .zip(stats(modifiers))
+            .any(|((_, value), (_, modifier))| value != 0 && value == modifier);
+        let saves = stats(&monster.saving_throws)
+            .into_iter()
+            .zip(stats(modifiers))
+            .flat_map(|((name, value), (_, modifier))| {
+                let expected = vec![modifier, modifier + proficiency_bonus];
+                match (value, modifier) {
+                    (0, 0) => None,
+                    (0, _) if !lists_untrained_saves => None,
+                    (0, _) => Some((
+                        format!("savingThrows.{name}"),
+                        AnomalyKind::Missing,
+                        value,
+                        expected,
+                    )),
+                    _ if expected.contains(&value) => None,
+                    _ => Some((
+                        format!("savingThrows.{name}"),
+                        AnomalyKind::Inconsistent,
+                        value,
+                        expected,
+                    )),
+                }
+            });
+        let skills = [
+            ("acrobatics", monster.skills.acrobatics, modifiers.dexterity),
+            (
+                "animalHandling",
+                monster.skills.animal_handling,
+                modifiers.wisdom,
+            ),
+            ("arcana", monster.skills.arcana, modifiers.intelligence),
+            ("athletics", monster.skills.athletics, modifiers.strength),
+            ("deception", monster.skills.deception, modifiers.charisma),
+            ("history", monster.skills.history, modifiers.intelligence),
+            ("insight", monster.skills.insight, modifiers.wisdom),
+            (
+                "intimidation",
+                monster.skills.intimidation,
+                modifiers.charisma,
+            ),
+            (
+                "investigation",
+                monster.skills.investigation,
+                modifiers.intelligence,
+            ),
+            ("medicine", monster.skills.medicine, modifiers.wisdom),
+            ("nature", monster.skills.nature, modifiers.intelligence),
+            ("perception", monster.skills.perception, modifiers.wisdom),
+            (
+                "performance",
+                monster.skills.performance,
+                modifiers.charisma,
+            ),
+            ("persuasion", monster.skills.persuasion, modifiers.charisma),
+            ("religion", monster.skills.religion, modifiers.intelligence),
+            (
+                "sleightOfHand",
+                monster.skills.sleight_of_hand,
+                modifiers.dexterity,
+            ),
+            ("stealth", monster.skills.stealth, modifiers.dexterity),
+            ("survival", monster.skills.survival, modifiers.wisdom),
+        ]
+        .into_iter()
+        .flat_map(|(name, value, modifier)| {
+            let expected = vec![
+                modifier,
+                modifier + proficiency_bonus,
+                modifier + 2 * proficiency_bonus,
+            ];
+            match value == 0 || expected.contains(&value) {
+                true => None,
+                false => Some((
+                    format!("skills.{name}"),
+                    AnomalyKind::Inconsistent,
+                    value,
+                    expected,
+                )),
+            }
+        });
+        saves
+            .chain(skills)
+            .map(|(field, kind, value, expected)| Anomaly {
+                monster: monster.name.clone(),
+                field,
+                kind,
+                value,
+                expected,
+            })
+            .collect()
+    }
+}
+
+fn stats(stats: &Stats) -> [(&'static str, i32); 6] {
+    [
+        ("str", stats.strength),
+        ("dex", stats.dexterity),
+        ("con", stats.constitution),
+        ("int", stats.intelligence),
+        ("wis", stats.wisdom),
+        ("cha", stats.charisma),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derived::derived_stats_calculator::DerivedStatsCalculatorImpl;
+    use crate::utilities::MONSTERS_JSON_PATH;
+    use crate::utilities::load_from_json::load_from_json;
+
+    fn validator() -> MonsterValidatorImpl {
+        MonsterValidatorImpl::new(Arc::new(DerivedStatsCalculatorImpl::default()))
+    }
+
+    fn monster(name: &str) -> Monster {
+        load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH)
+            .into_iter()
+            .find(|monster| monster.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validate_consistent_monster() {
+        let mut aboleth = monster("Aboleth");
+        aboleth.saving_throws = aboleth.modifiers.clone();
+
+        assert!(validator().validate(&aboleth).is_empty());
+    }
+
+    #[test]
+    fn test_validate_treats_zero_saves_as_untrained() {
+        let aboleth = monster("Aboleth");
+
+        assert!(validator().validate(&aboleth).is_empty());
+    }
+
+    #[test]
+    fn test_validate_monster_data() {
+        let anomalies = load_from_json::<Vec<Monster>>(MONSTERS_JSON_PATH)
+            .iter()
+            .flat_map(|monster| validator().validate(monster))
+            .collect::<Vec<_>>();
+
+        assert_eq!(anomalies.len(), 8);
+        assert!(
+            anomalies
+                .iter()
+                .all(|anomaly| anomaly.kind == AnomalyKind::Inconsistent)
+        );
+    }
+
+    #[test]
+    fn test_validate_flags_anomalies() {
+        let mut aboleth = monster("Aboleth");
+        aboleth.saving_throws = aboleth.modifiers.clone();
+        aboleth.saving_throws.constitution = 0;
+        aboleth.saving_throws.wisdom = 9;
+        aboleth.skills.history = 11;
+
+        let anomalies = validator().validate(&aboleth);
+
+        assert_eq!(
+            anomalies,
+            vec![
+                Anomaly {
+                    monster: "Aboleth".to_string(),
+                    field: "savingThrows.con".to_string(),
+                    kind: AnomalyKind::Missing,
+                    value: 0,
+                    expected: vec![2, 6],
+                },
+                Anomaly {
+                    monster: "Aboleth".to_string(),
+                    field: "savingThrows.wis".to_string(),
+                    kind: AnomalyKind::Inconsistent,
+                    value: 9,
+                    expected: vec![2, 6],
+                },
+                Anomaly {
+                    monster: "Aboleth".to_string(),
+                    field: "skills.history".to_string(),
+                    kind: AnomalyKind::Inconsistent,
+                    value: 11,
+                    expected: vec![4, 8, 12],
+                },
+            ]
+        );
+    }
+}
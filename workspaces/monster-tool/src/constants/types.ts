@@ -101,6 +101,11 @@ export interface TransformedMonster {
   };
   readonly legendaryActions: string[];
   readonly reactions: string[];
+  readonly damageModifiers: {
+    readonly resistances: string[];
+    readonly vulnerabilities: string[];
+    readonly immunities: string[];
+  };
   readonly challenge: {
     readonly rating: string;
     readonly xp: number;
@@ -0,0 +1,76 @@
+import { RawMonster } from '../constants/types';
+import { transformDamageModifiers } from './transform-damage-modifiers';
+
+const rawMonster: RawMonster = {
+  name: 'Test Monster',
+  meta: 'Medium humanoid, neutral',
+  'Armor Class': '12',
+  'Hit Points': '22 (4d8 + 4)',
+  Speed: '30 ft.',
+  STR: '10',
+  STR_mod: '(+0)',
+  DEX: '14',
+  DEX_mod: '(+2)',
+  CON: '12',
+  CON_mod: '(+1)',
+  INT: '10',
+  INT_mod: '(+0)',
+  WIS: '10',
+  WIS_mod: '(+0)',
+  CHA: '10',
+  CHA_mod: '(+0)',
+  Senses: 'Passive Perception 10',
+  Languages: 'Common',
+  Challenge: '1 (200 XP)',
+  img_url: '',
+};
+
+describe('transformDamageModifiers', () => {
+  it('returns empty lists when the monster has no damage modifiers', () => {
+    expect(transformDamageModifiers(rawMonster)).toEqual({
+      resistances: [],
+      vulnerabilities: [],
+      immunities: [],
+    });
+  });
+
+  it('parses plain comma-separated damage types', () => {
+    expect(
+      transformDamageModifiers({
+        ...rawMonster,
+        'Damage Resistances': 'Fire, Cold',
+        'Damage Vulnerabilities': 'Radiant',
+        'Damage Immunities': 'Poison',
+      }),
+    ).toEqual({
+      resistances: ['fire', 'cold'],
+      vulnerabilities: ['radiant'],
+      immunities: ['poison'],
+    });
+  });
+
+  it('parses every semicolon-separated clause', () => {
+    expect(
+      transformDamageModifiers({
+        ...rawMonster,
+        'Damage Resistances': 'Acid, Cold; Lightning',
+      }).resistances,
+    ).toEqual(['acid', 'cold', 'lightning']);
+  });
+
+  it('skips conditional nonmagical clauses', () => {
+    expect(
+      transformDamageModifiers({
+        ...rawMonster,
+        'Damage Resistances':
+          'Lightning, Thunder; Bludgeoning, Piercing, and Slashing from Nonmagical Attacks',
+        'Damage Immunities':
+          "Bludgeoning, Piercing, and Slashing from Nonmagical Attacks that aren't Adamantine",
+      }),
+    ).toEqual({
+      resistances: ['lightning', 'thunder'],
+      vulnerabilities: [],
+      immunities: [],
+    });
+  });
+});
@@ -0,0 +1,33 @@
+import { RawMonster, TransformedMonster } from '../constants/types';
+
+const DAMAGE_TYPES = [
+  'bludgeoning',
+  'piercing',
+  'slashing',
+  'acid',
+  'lightning',
+  'poison',
+  'fire',
+  'cold',
+  'radiant',
+  'necrotic',
+  'thunder',
+  'force',
+  'psychic',
+];
+
+export const transformDamageModifiers = (
+  monster: RawMonster,
+): TransformedMonster['damageModifiers'] => ({
+  resistances: extractDamageTypes(monster['Damage Resistances']),
+  vulnerabilities: extractDamageTypes(monster['Damage Vulnerabilities']),
+  immunities: extractDamageTypes(monster['Damage Immunities']),
+});
+
+const extractDamageTypes = (damageExpression?: string): string[] =>
+  (damageExpression ?? '')
+    .toLowerCase()
+    .split(';')
+    .filter((clause) => !/\bfrom\b|nonmagical/.test(clause))
+    .flatMap((clause) => clause.match(/[a-z]+/g) ?? [])
+    .filter((word) => DAMAGE_TYPES.includes(word));
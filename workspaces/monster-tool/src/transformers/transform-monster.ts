@@ -16,6 +16,7 @@ import { transformActions } from './transform-actions';
 import { transformLegendaryActions } from './transform-legendary-actions';
 import { transformReactions } from './transform-reactions';
 import { transformChallenge } from './transform-challenge';
+import { transformDamageModifiers } from './transform-damage-modifiers';
 
 export const transformMonster = async (
   monster: RawMonster,
@@ -37,6 +38,7 @@ export const transformMonster = async (
   actions: await transformActions(monster),
   legendaryActions: transformLegendaryActions(monster),
   reactions: transformReactions(monster),
+  damageModifiers: transformDamageModifiers(monster),
   challenge: transformChallenge(monster),
   imageUrl: monster.img_url,
 });